@@ -9,11 +9,14 @@ use regex::Regex;
 
 const MIN_PHP_API_VER: u32 = 20200930;
 const MAX_PHP_API_VER: u32 = 20200930;
+const PHP_81_API_VER: u32 = 20210902;
 
 fn main() {
     // rerun if wrapper header is changed
     println!("cargo:rerun-if-changed=src/wrapper/wrapper.h");
     println!("cargo:rerun-if-changed=src/wrapper/wrapper.c");
+    // declare the version cfgs, which are only set for some versions of PHP
+    println!("cargo:rustc-check-cfg=cfg(php81)");
 
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
 
@@ -66,6 +69,10 @@ fn main() {
         panic!("The current version of PHP is not supported. Current PHP API version: {}, requires a version between {} and {}", api_ver, MIN_PHP_API_VER, MAX_PHP_API_VER);
     }
 
+    if api_ver >= PHP_81_API_VER {
+        println!("cargo:rustc-cfg=php81");
+    }
+
     let includes =
         String::from_utf8(includes_cmd.stdout).expect("unable to parse `php-config` stdout");
 
@@ -148,35 +155,113 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "HashTable",
     "_Bucket",
     "_call_user_function_impl",
+    "_is_numeric_string_ex",
     "_efree",
     "_emalloc",
+    "_erealloc",
+    "_php_stream_flush",
+    "_php_stream_free",
+    "_php_stream_read",
+    "_php_stream_seek",
+    "_php_stream_tell",
+    "_php_stream_write",
     "_zend_executor_globals",
     "_zend_expected_type",
     "_zend_expected_type_Z_EXPECTED_ARRAY",
+    "_zend_expected_type_Z_EXPECTED_ARRAY_OR_LONG",
+    "_zend_expected_type_Z_EXPECTED_ARRAY_OR_STRING",
     "_zend_expected_type_Z_EXPECTED_BOOL",
     "_zend_expected_type_Z_EXPECTED_DOUBLE",
+    "_zend_expected_type_Z_EXPECTED_FUNC",
+    "_zend_expected_type_Z_EXPECTED_ITERABLE",
     "_zend_expected_type_Z_EXPECTED_LONG",
+    "_zend_expected_type_Z_EXPECTED_NUMBER",
     "_zend_expected_type_Z_EXPECTED_OBJECT",
+    "_zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING",
     "_zend_expected_type_Z_EXPECTED_RESOURCE",
     "_zend_expected_type_Z_EXPECTED_STRING",
+    "_zend_expected_type_Z_EXPECTED_STRING_OR_LONG",
     "_zend_new_array",
     "_zval_struct__bindgen_ty_1",
     "_zval_struct__bindgen_ty_2",
+    "display_ini_entries",
     "ext_php_rs_executor_globals",
+    "ext_php_rs_info_print_box",
+    "get_active_class_name",
+    "get_active_function_name",
+    "ext_php_rs_pefree",
+    "ext_php_rs_pemalloc",
     "ext_php_rs_php_build_id",
+    "ext_php_rs_php_stream_alloc",
+    "ext_php_rs_php_stream_bucket_new",
+    "ext_php_rs_php_stream_filter_alloc",
+    "ext_php_rs_php_stream_open_wrapper",
+    "ext_php_rs_php_stream_statbuf_init",
+    "ext_php_rs_php_stream_to_zval",
+    "ext_php_rs_sapi_globals",
     "ext_php_rs_zend_object_alloc",
     "ext_php_rs_zend_object_release",
     "ext_php_rs_zend_string_init",
     "ext_php_rs_zend_string_release",
+    "ext_php_rs_zval_make_ref",
+    "ext_php_rs_zval_persist",
+    "ext_php_rs_zval_persist_release",
+    "ext_php_rs_zval_separate",
+    "module_registry",
+    "object_init_ex",
     "object_properties_init",
+    "php_file_le_pstream",
+    "php_file_le_stream",
+    "php_info_print_hr",
+    "php_info_print_table_colspan_header",
     "php_info_print_table_end",
     "php_info_print_table_header",
     "php_info_print_table_row",
     "php_info_print_table_start",
+    "php_le_stream_context",
+    "php_output_clean",
+    "php_output_discard",
+    "php_output_end",
+    "php_output_flush",
+    "php_output_get_contents",
+    "php_output_get_level",
+    "php_output_handler_create_internal",
+    "php_output_handler_free",
+    "php_output_handler_set_context",
+    "php_output_handler_start",
+    "php_output_start_default",
+    "php_output_write",
+    "php_output_write_unbuffered",
+    "php_register_url_stream_wrapper",
+    "php_stream",
+    "php_stream_bucket",
+    "php_stream_bucket_append",
+    "php_stream_bucket_brigade",
+    "php_stream_bucket_delref",
+    "php_stream_bucket_unlink",
+    "php_stream_context",
+    "php_stream_context_alloc",
+    "php_stream_context_get_option",
+    "php_stream_context_set_option",
+    "php_stream_dirent",
+    "php_stream_filter",
+    "php_stream_filter_factory",
+    "php_stream_filter_ops",
+    "php_stream_filter_register_factory",
+    "php_stream_filter_status_t",
+    "php_stream_filter_unregister_factory",
+    "php_stream_ops",
+    "php_stream_statbuf",
+    "php_stream_wrapper",
+    "php_stream_wrapper_log_error",
+    "php_stream_wrapper_ops",
+    "php_unregister_url_stream_wrapper",
     "std_object_handlers",
+    "tsrm_get_ls_cache",
     "zend_array_destroy",
     "zend_array_dup",
     "zend_ce_argument_count_error",
+    "zend_ce_error",
     "zend_ce_arithmetic_error",
     "zend_ce_compile_error",
     "zend_ce_division_by_zero_error",
@@ -192,7 +277,11 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "zend_declare_property",
     "zend_do_implement_interface",
     "zend_execute_data",
+    "zend_fetch_debug_backtrace",
     "zend_function_entry",
+    "zend_get_executed_filename_ex",
+    "zend_get_executed_lineno",
+    "zend_module_dep",
     "zend_hash_clean",
     "zend_hash_index_del",
     "zend_hash_index_find",
@@ -203,31 +292,49 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "zend_hash_str_update",
     "zend_internal_arg_info",
     "zend_is_callable",
+    "zend_list_delete",
     "zend_long",
     "zend_lookup_class_ex",
     "zend_module_entry",
     "zend_object",
     "zend_object_handlers",
     "zend_object_std_init",
+    "zend_observer_fcall_handlers",
+    "zend_observer_fcall_init",
+    "zend_observer_fcall_register",
     "zend_objects_clone_members",
     "zend_register_bool_constant",
     "zend_register_double_constant",
     "zend_register_internal_class_ex",
     "zend_register_long_constant",
     "zend_register_string_constant",
+    "zend_constant",
+    "zend_get_constant_ex",
+    "zend_get_constant_str",
+    "zend_register_constant",
     "zend_resource",
     "zend_string",
     "zend_string_init_interned",
     "zend_throw_exception_ex",
     "zend_type",
+    "zend_update_class_constants",
+    "zend_type_list",
     "zend_value",
+    "zend_zval_type_name",
     "zend_wrong_parameters_count_error",
+    "zend_argument_error",
+    "zend_argument_type_error",
+    "zend_error",
     "zval",
+    "zval_get_string_func",
+    "E_DEPRECATED",
+    "E_WARNING",
     "CONST_CS",
     "CONST_DEPRECATED",
     "CONST_NO_FILE_CACHE",
     "CONST_PERSISTENT",
     "HT_MIN_SIZE",
+    "IGNORE_URL",
     "IS_ARRAY",
     "IS_ARRAY_EX",
     "IS_CALLABLE",
@@ -236,6 +343,7 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "IS_DOUBLE",
     "IS_FALSE",
     "IS_INTERNED_STRING_EX",
+    "IS_ITERABLE",
     "IS_LONG",
     "IS_MIXED",
     "IS_NULL",
@@ -245,6 +353,7 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "IS_REFERENCE_EX",
     "IS_RESOURCE",
     "IS_RESOURCE_EX",
+    "IS_STATIC",
     "IS_STRING",
     "IS_STRING_EX",
     "IS_TRUE",
@@ -255,6 +364,29 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "IS_PTR",
     "MAY_BE_ANY",
     "MAY_BE_BOOL",
+    "MODULE_DEP_CONFLICTS",
+    "MODULE_DEP_OPTIONAL",
+    "MODULE_DEP_REQUIRED",
+    "PHP_OUTPUT_HANDLER_CLEAN",
+    "PHP_OUTPUT_HANDLER_CLEANABLE",
+    "PHP_OUTPUT_HANDLER_FINAL",
+    "PHP_OUTPUT_HANDLER_FLUSH",
+    "PHP_OUTPUT_HANDLER_FLUSHABLE",
+    "PHP_OUTPUT_HANDLER_REMOVABLE",
+    "PHP_OUTPUT_HANDLER_START",
+    "PHP_OUTPUT_HANDLER_STDFLAGS",
+    "PHP_STREAM_FREE_CLOSE",
+    "PHP_STREAM_MKDIR_RECURSIVE",
+    "PHP_USER_CONSTANT",
+    "PSFS_FLAG_FLUSH_CLOSE",
+    "PSFS_FLAG_FLUSH_INC",
+    "PSFS_FLAG_NORMAL",
+    "REPORT_ERRORS",
+    "SEEK_CUR",
+    "SEEK_END",
+    "SEEK_SET",
+    "STREAM_MUST_SEEK",
+    "USE_PATH",
     "USING_ZTS",
     "ZEND_ACC_ABSTRACT",
     "ZEND_ACC_ANON_CLASS",
@@ -300,7 +432,9 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "ZEND_ACC_USES_THIS",
     "ZEND_ACC_USE_GUARDS",
     "ZEND_ACC_VARIADIC",
+    "ZEND_CALL_HAS_EXTRA_NAMED_PARAMS",
     "ZEND_DEBUG",
+    "ZEND_FETCH_CLASS_SILENT",
     "ZEND_HAS_STATIC_IN_METHODS",
     "ZEND_ISEMPTY",
     "ZEND_MM_ALIGNMENT",
@@ -314,6 +448,8 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "_ZEND_SEND_MODE_SHIFT",
     "_ZEND_TYPE_NULLABLE_BIT",
     "ts_rsrc_id",
+    "_ZEND_TYPE_INTERSECTION_BIT",
+    "_ZEND_TYPE_LIST_BIT",
     "_ZEND_TYPE_NAME_BIT",
     "zval_ptr_dtor",
     "zend_refcounted_h",
@@ -322,5 +458,14 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "zend_std_read_property",
     "zend_std_write_property",
     "zend_std_get_properties",
+    "zend_std_get_debug_info",
     "zend_std_has_property",
+    "sapi_globals_struct",
+    "sapi_header_line",
+    "sapi_header_op",
+    "sapi_header_op_enum",
+    "sapi_header_struct",
+    "zend_is_auto_global_str",
+    "zend_call_function",
+    "zend_fcall_info",
 ];
@@ -30,7 +30,9 @@ pub const IS_RESOURCE: u32 = 9;
 pub const IS_REFERENCE: u32 = 10;
 pub const IS_CONSTANT_AST: u32 = 11;
 pub const IS_CALLABLE: u32 = 12;
+pub const IS_ITERABLE: u32 = 13;
 pub const IS_VOID: u32 = 14;
+pub const IS_STATIC: u32 = 15;
 pub const IS_MIXED: u32 = 16;
 pub const IS_PTR: u32 = 13;
 pub const _IS_BOOL: u32 = 17;
@@ -92,18 +94,49 @@ pub const ZEND_ACC_GENERATOR: u32 = 16777216;
 pub const ZEND_ACC_DONE_PASS_TWO: u32 = 33554432;
 pub const ZEND_ACC_HEAP_RT_CACHE: u32 = 67108864;
 pub const ZEND_ACC_STRICT_TYPES: u32 = 2147483648;
+pub const E_WARNING: u32 = 2;
+pub const E_DEPRECATED: u32 = 8192;
+pub const PHP_OUTPUT_HANDLER_START: u32 = 1;
+pub const PHP_OUTPUT_HANDLER_CLEAN: u32 = 2;
+pub const PHP_OUTPUT_HANDLER_FLUSH: u32 = 4;
+pub const PHP_OUTPUT_HANDLER_FINAL: u32 = 8;
+pub const PHP_OUTPUT_HANDLER_CLEANABLE: u32 = 16;
+pub const PHP_OUTPUT_HANDLER_FLUSHABLE: u32 = 32;
+pub const PHP_OUTPUT_HANDLER_REMOVABLE: u32 = 64;
+pub const PHP_OUTPUT_HANDLER_STDFLAGS: u32 = 112;
+pub const SEEK_SET: u32 = 0;
+pub const SEEK_CUR: u32 = 1;
+pub const SEEK_END: u32 = 2;
+pub const USE_PATH: u32 = 1;
+pub const IGNORE_URL: u32 = 2;
+pub const REPORT_ERRORS: u32 = 8;
+pub const PSFS_FLAG_NORMAL: u32 = 0;
+pub const PSFS_FLAG_FLUSH_INC: u32 = 1;
+pub const PSFS_FLAG_FLUSH_CLOSE: u32 = 2;
+pub const STREAM_MUST_SEEK: u32 = 16;
+pub const PHP_STREAM_FREE_CLOSE: u32 = 3;
+pub const PHP_STREAM_MKDIR_RECURSIVE: u32 = 1;
+pub const ZEND_CALL_HAS_EXTRA_NAMED_PARAMS: u32 = 134217728;
 pub const ZEND_ISEMPTY: u32 = 1;
 pub const _ZEND_SEND_MODE_SHIFT: u32 = 24;
 pub const _ZEND_IS_VARIADIC_BIT: u32 = 67108864;
 pub const ZEND_MODULE_API_NO: u32 = 20200930;
 pub const USING_ZTS: u32 = 0;
+pub const MODULE_DEP_REQUIRED: u32 = 1;
+pub const MODULE_DEP_CONFLICTS: u32 = 2;
+pub const MODULE_DEP_OPTIONAL: u32 = 3;
 pub const MAY_BE_BOOL: u32 = 12;
 pub const MAY_BE_ANY: u32 = 1022;
 pub const CONST_CS: u32 = 0;
 pub const CONST_PERSISTENT: u32 = 1;
 pub const CONST_NO_FILE_CACHE: u32 = 2;
 pub const CONST_DEPRECATED: u32 = 4;
+pub const PHP_USER_CONSTANT: u32 = 8388607;
+pub const ZEND_FETCH_CLASS_SILENT: u32 = 256;
 pub type size_t = ::std::os::raw::c_ulong;
+pub type __ssize_t = ::std::os::raw::c_long;
+pub type ssize_t = __ssize_t;
+pub type zend_off_t = i64;
 pub type __uint8_t = ::std::os::raw::c_uchar;
 pub type __uint16_t = ::std::os::raw::c_ushort;
 pub type __uint32_t = ::std::os::raw::c_uint;
@@ -315,6 +348,16 @@ extern "C" {
         __zend_orig_lineno: u32,
     ) -> *mut ::std::os::raw::c_void;
 }
+extern "C" {
+    pub fn _erealloc(
+        ptr: *mut ::std::os::raw::c_void,
+        size: size_t,
+        __zend_filename: *const ::std::os::raw::c_char,
+        __zend_lineno: u32,
+        __zend_orig_filename: *const ::std::os::raw::c_char,
+        __zend_orig_lineno: u32,
+    ) -> *mut ::std::os::raw::c_void;
+}
 extern "C" {
     pub fn _efree(
         ptr: *mut ::std::os::raw::c_void,
@@ -791,6 +834,12 @@ extern "C" {
         cache_slot: *mut *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_int;
 }
+extern "C" {
+    pub fn zend_std_get_debug_info(
+        object: *mut zend_object,
+        is_temp: *mut ::std::os::raw::c_int,
+    ) -> *mut HashTable;
+}
 extern "C" {
     pub fn zend_is_true(op: *mut zval) -> ::std::os::raw::c_int;
 }
@@ -974,9 +1023,41 @@ pub struct _zend_stack {
     pub elements: *mut ::std::os::raw::c_void,
 }
 pub type zend_stack = _zend_stack;
+extern "C" {
+    pub fn zend_get_executed_filename_ex() -> *mut zend_string;
+}
+extern "C" {
+    pub fn zend_get_executed_lineno() -> u32;
+}
+extern "C" {
+    pub fn zend_fetch_debug_backtrace(
+        return_value: *mut zval,
+        skip_last: ::std::os::raw::c_int,
+        options: ::std::os::raw::c_int,
+        limit: ::std::os::raw::c_int,
+    );
+}
 extern "C" {
     pub fn zend_object_std_init(object: *mut zend_object, ce: *mut zend_class_entry);
 }
+pub type zend_observer_fcall_begin_handler =
+    ::std::option::Option<unsafe extern "C" fn(execute_data: *mut zend_execute_data)>;
+pub type zend_observer_fcall_end_handler = ::std::option::Option<
+    unsafe extern "C" fn(execute_data: *mut zend_execute_data, retval: *mut zval),
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_observer_fcall_handlers {
+    pub begin: zend_observer_fcall_begin_handler,
+    pub end: zend_observer_fcall_end_handler,
+}
+pub type zend_observer_fcall_handlers = _zend_observer_fcall_handlers;
+pub type zend_observer_fcall_init = ::std::option::Option<
+    unsafe extern "C" fn(execute_data: *mut zend_execute_data) -> zend_observer_fcall_handlers,
+>;
+extern "C" {
+    pub fn zend_observer_fcall_register(arg1: zend_observer_fcall_init);
+}
 extern "C" {
     pub fn zend_objects_clone_members(new_object: *mut zend_object, old_object: *mut zend_object);
 }
@@ -1123,6 +1204,10 @@ pub struct _zend_module_dep {
     pub version: *const ::std::os::raw::c_char,
     pub type_: ::std::os::raw::c_uchar,
 }
+pub type zend_module_dep = _zend_module_dep;
+extern "C" {
+    pub static mut module_registry: HashTable;
+}
 extern "C" {
     pub fn zend_lookup_class_ex(
         name: *mut zend_string,
@@ -1177,6 +1262,12 @@ extern "C" {
         value: *mut zval,
     );
 }
+extern "C" {
+    pub fn object_init_ex(arg: *mut zval, ce: *mut zend_class_entry) -> zend_result;
+}
+extern "C" {
+    pub fn zend_update_class_constants(class_type: *mut zend_class_entry) -> zend_result;
+}
 extern "C" {
     pub fn object_properties_init(object: *mut zend_object, class_type: *mut zend_class_entry);
 }
@@ -1227,6 +1318,45 @@ pub type _zend_expected_type = ::std::os::raw::c_uint;
 extern "C" {
     pub fn zend_wrong_parameters_count_error(min_num_args: u32, max_num_args: u32);
 }
+extern "C" {
+    pub fn zend_argument_error(
+        error_ce: *mut zend_class_entry,
+        arg_num: u32,
+        format: *const ::std::os::raw::c_char,
+        ...
+    );
+}
+extern "C" {
+    pub fn zend_argument_type_error(arg_num: u32, format: *const ::std::os::raw::c_char, ...);
+}
+extern "C" {
+    pub fn zend_zval_type_name(arg: *const zval) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn zend_error(type_: ::std::os::raw::c_int, format: *const ::std::os::raw::c_char, ...);
+}
+extern "C" {
+    pub fn get_active_class_name(
+        space: *mut *const ::std::os::raw::c_char,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn get_active_function_name() -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn zval_get_string_func(op: *mut zval) -> *mut zend_string;
+}
+extern "C" {
+    pub fn _is_numeric_string_ex(
+        str_: *const ::std::os::raw::c_char,
+        length: size_t,
+        lval: *mut zend_long,
+        dval: *mut f64,
+        allow_errors: bool,
+        oflow_info: *mut ::std::os::raw::c_int,
+        trailing_data: *mut bool,
+    ) -> zend_uchar;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _zend_ini_entry {
@@ -1290,6 +1420,28 @@ extern "C" {
         module_number: ::std::os::raw::c_int,
     );
 }
+#[repr(C)]
+pub struct _zend_constant {
+    pub value: zval,
+    pub name: *mut zend_string,
+}
+pub type zend_constant = _zend_constant;
+extern "C" {
+    pub fn zend_register_constant(c: *mut zend_constant) -> zend_result;
+}
+extern "C" {
+    pub fn zend_get_constant_str(
+        name: *const ::std::os::raw::c_char,
+        name_len: size_t,
+    ) -> *mut zval;
+}
+extern "C" {
+    pub fn zend_get_constant_ex(
+        name: *mut zend_string,
+        scope: *mut zend_class_entry,
+        flags: u32,
+    ) -> *mut zval;
+}
 extern "C" {
     pub fn php_info_print_table_header(num_cols: ::std::os::raw::c_int, ...);
 }
@@ -1299,6 +1451,18 @@ extern "C" {
 extern "C" {
     pub fn php_info_print_table_start();
 }
+extern "C" {
+    pub fn php_info_print_hr();
+}
+extern "C" {
+    pub fn php_info_print_table_colspan_header(
+        num_cols: ::std::os::raw::c_int,
+        header: *const ::std::os::raw::c_char,
+    );
+}
+extern "C" {
+    pub fn display_ini_entries(module: *mut zend_module_entry);
+}
 extern "C" {
     pub fn php_info_print_table_end();
 }
@@ -1308,6 +1472,9 @@ extern "C" {
 extern "C" {
     pub static mut zend_ce_exception: *mut zend_class_entry;
 }
+extern "C" {
+    pub static mut zend_ce_error: *mut zend_class_entry;
+}
 extern "C" {
     pub static mut zend_ce_error_exception: *mut zend_class_entry;
 }
@@ -1371,3 +1538,709 @@ extern "C" {
 extern "C" {
     pub fn ext_php_rs_executor_globals() -> *mut zend_executor_globals;
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_output_buffer {
+    pub data: *mut ::std::os::raw::c_char,
+    pub size: size_t,
+    pub used: size_t,
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: [u8; 4usize],
+}
+pub type php_output_buffer = _php_output_buffer;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_output_context {
+    pub op: ::std::os::raw::c_int,
+    pub in_: php_output_buffer,
+    pub out: php_output_buffer,
+}
+pub type php_output_context = _php_output_context;
+pub type php_output_handler_context_func_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        handler_context: *mut *mut ::std::os::raw::c_void,
+        output_context: *mut php_output_context,
+    ) -> ::std::os::raw::c_int,
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_output_handler {
+    _unused: [u8; 0],
+}
+pub type php_output_handler = _php_output_handler;
+extern "C" {
+    pub fn php_output_write(str_: *const ::std::os::raw::c_char, len: size_t) -> size_t;
+}
+extern "C" {
+    pub fn php_output_write_unbuffered(str_: *const ::std::os::raw::c_char, len: size_t) -> size_t;
+}
+extern "C" {
+    pub fn php_output_flush() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_clean() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_end() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_discard() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_get_contents(p: *mut zval) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_get_level() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_start_default() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_handler_create_internal(
+        name: *const ::std::os::raw::c_char,
+        name_len: size_t,
+        handler: php_output_handler_context_func_t,
+        chunk_size: size_t,
+        flags: ::std::os::raw::c_int,
+    ) -> *mut php_output_handler;
+}
+extern "C" {
+    pub fn php_output_handler_set_context(
+        handler: *mut php_output_handler,
+        opaq: *mut ::std::os::raw::c_void,
+        dtor: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    );
+}
+extern "C" {
+    pub fn php_output_handler_start(handler: *mut php_output_handler) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_output_handler_free(handler: *mut *mut php_output_handler);
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _IO_FILE {
+    _unused: [u8; 0],
+}
+pub type FILE = _IO_FILE;
+pub type php_stream = _php_stream;
+pub type php_stream_wrapper = _php_stream_wrapper;
+pub type php_stream_context = _php_stream_context;
+pub type php_stream_filter = _php_stream_filter;
+pub type php_stream_notifier = _php_stream_notifier;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct stat {
+    _unused: [u8; 0],
+}
+pub type zend_stat_t = stat;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_statbuf {
+    pub sb: zend_stat_t,
+}
+pub type php_stream_statbuf = _php_stream_statbuf;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _php_stream_dirent {
+    pub d_name: [::std::os::raw::c_char; 4096usize],
+}
+pub type php_stream_dirent = _php_stream_dirent;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_ops {
+    pub write: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            buf: *const ::std::os::raw::c_char,
+            count: size_t,
+        ) -> ssize_t,
+    >,
+    pub read: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            buf: *mut ::std::os::raw::c_char,
+            count: size_t,
+        ) -> ssize_t,
+    >,
+    pub close: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            close_handle: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub flush: ::std::option::Option<
+        unsafe extern "C" fn(stream: *mut php_stream) -> ::std::os::raw::c_int,
+    >,
+    pub label: *const ::std::os::raw::c_char,
+    pub seek: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            offset: zend_off_t,
+            whence: ::std::os::raw::c_int,
+            newoffset: *mut zend_off_t,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub cast: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            castas: ::std::os::raw::c_int,
+            ret: *mut *mut ::std::os::raw::c_void,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub stat: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            ssb: *mut php_stream_statbuf,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub set_option: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            option: ::std::os::raw::c_int,
+            value: ::std::os::raw::c_int,
+            ptrparam: *mut ::std::os::raw::c_void,
+        ) -> ::std::os::raw::c_int,
+    >,
+}
+pub type php_stream_ops = _php_stream_ops;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_wrapper_ops {
+    pub wrapper_open: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            filename: *const ::std::os::raw::c_char,
+            mode: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            opened_path: *mut *mut zend_string,
+            context: *mut php_stream_context,
+            __php_stream_call_depth: ::std::os::raw::c_int,
+            __zend_filename: *const ::std::os::raw::c_char,
+            __zend_lineno: u32,
+            __zend_orig_filename: *const ::std::os::raw::c_char,
+            __zend_orig_lineno: u32,
+        ) -> *mut php_stream,
+    >,
+    pub wrapper_close: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            stream: *mut php_stream,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub wrapper_stat: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            stream: *mut php_stream,
+            ssb: *mut php_stream_statbuf,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub url_stat: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url: *const ::std::os::raw::c_char,
+            flags: ::std::os::raw::c_int,
+            ssb: *mut php_stream_statbuf,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub dir_opener: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            filename: *const ::std::os::raw::c_char,
+            mode: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            opened_path: *mut *mut zend_string,
+            context: *mut php_stream_context,
+            __php_stream_call_depth: ::std::os::raw::c_int,
+            __zend_filename: *const ::std::os::raw::c_char,
+            __zend_lineno: u32,
+            __zend_orig_filename: *const ::std::os::raw::c_char,
+            __zend_orig_lineno: u32,
+        ) -> *mut php_stream,
+    >,
+    pub label: *const ::std::os::raw::c_char,
+    pub unlink: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub rename: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url_from: *const ::std::os::raw::c_char,
+            url_to: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub stream_mkdir: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url: *const ::std::os::raw::c_char,
+            mode: ::std::os::raw::c_int,
+            options: ::std::os::raw::c_int,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub stream_rmdir: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub stream_metadata: ::std::option::Option<
+        unsafe extern "C" fn(
+            wrapper: *mut php_stream_wrapper,
+            url: *const ::std::os::raw::c_char,
+            options: ::std::os::raw::c_int,
+            value: *mut ::std::os::raw::c_void,
+            context: *mut php_stream_context,
+        ) -> ::std::os::raw::c_int,
+    >,
+}
+pub type php_stream_wrapper_ops = _php_stream_wrapper_ops;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_wrapper {
+    pub wops: *const php_stream_wrapper_ops,
+    pub abstract_: *mut ::std::os::raw::c_void,
+    pub is_url: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_notifier {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_filter_chain {
+    pub head: *mut php_stream_filter,
+    pub tail: *mut php_stream_filter,
+    pub stream: *mut php_stream,
+}
+pub type php_stream_filter_chain = _php_stream_filter_chain;
+pub type php_stream_bucket = _php_stream_bucket;
+pub type php_stream_bucket_brigade = _php_stream_bucket_brigade;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_bucket {
+    pub next: *mut php_stream_bucket,
+    pub prev: *mut php_stream_bucket,
+    pub brigade: *mut php_stream_bucket_brigade,
+    pub buf: *mut ::std::os::raw::c_char,
+    pub buflen: size_t,
+    pub own_buf: u8,
+    pub is_persistent: u8,
+    pub refcount: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_bucket_brigade {
+    pub head: *mut php_stream_bucket,
+    pub tail: *mut php_stream_bucket,
+}
+pub const php_stream_filter_status_t_PSFS_ERR_FATAL: php_stream_filter_status_t = 0;
+pub const php_stream_filter_status_t_PSFS_FEED_ME: php_stream_filter_status_t = 1;
+pub const php_stream_filter_status_t_PSFS_PASS_ON: php_stream_filter_status_t = 2;
+pub type php_stream_filter_status_t = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_filter_ops {
+    pub filter: ::std::option::Option<
+        unsafe extern "C" fn(
+            stream: *mut php_stream,
+            thisfilter: *mut php_stream_filter,
+            buckets_in: *mut php_stream_bucket_brigade,
+            buckets_out: *mut php_stream_bucket_brigade,
+            bytes_consumed: *mut size_t,
+            flags: ::std::os::raw::c_int,
+        ) -> php_stream_filter_status_t,
+    >,
+    pub dtor: ::std::option::Option<unsafe extern "C" fn(thisfilter: *mut php_stream_filter)>,
+    pub label: *const ::std::os::raw::c_char,
+}
+pub type php_stream_filter_ops = _php_stream_filter_ops;
+#[repr(C)]
+pub struct _php_stream_filter {
+    pub fops: *const php_stream_filter_ops,
+    pub abstract_: zval,
+    pub next: *mut php_stream_filter,
+    pub prev: *mut php_stream_filter,
+    pub is_persistent: ::std::os::raw::c_int,
+    pub chain: *mut php_stream_filter_chain,
+    pub buffer: php_stream_bucket_brigade,
+    pub res: *mut zend_resource,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _php_stream_filter_factory {
+    pub create_filter: ::std::option::Option<
+        unsafe extern "C" fn(
+            filtername: *const ::std::os::raw::c_char,
+            filterparams: *mut zval,
+            persistent: u8,
+        ) -> *mut php_stream_filter,
+    >,
+}
+pub type php_stream_filter_factory = _php_stream_filter_factory;
+extern "C" {
+    pub fn php_stream_bucket_delref(bucket: *mut php_stream_bucket);
+}
+extern "C" {
+    pub fn php_stream_bucket_unlink(bucket: *mut php_stream_bucket);
+}
+extern "C" {
+    pub fn php_stream_bucket_append(
+        brigade: *mut php_stream_bucket_brigade,
+        bucket: *mut php_stream_bucket,
+    );
+}
+extern "C" {
+    pub fn php_stream_filter_register_factory(
+        filterpattern: *const ::std::os::raw::c_char,
+        factory: *const php_stream_filter_factory,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_stream_filter_unregister_factory(
+        filterpattern: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+pub struct _php_stream {
+    pub ops: *const php_stream_ops,
+    pub abstract_: *mut ::std::os::raw::c_void,
+    pub readfilters: php_stream_filter_chain,
+    pub writefilters: php_stream_filter_chain,
+    pub wrapper: *mut php_stream_wrapper,
+    pub wrapperthis: *mut ::std::os::raw::c_void,
+    pub wrapperdata: zval,
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: [u8; 1usize],
+    pub fgetss_state: u8,
+    pub mode: [::std::os::raw::c_char; 16usize],
+    pub flags: u32,
+    pub res: *mut zend_resource,
+    pub stdiocast: *mut FILE,
+    pub orig_path: *mut ::std::os::raw::c_char,
+    pub ctx: *mut zend_resource,
+    pub position: zend_off_t,
+    pub readbuf: *mut ::std::os::raw::c_uchar,
+    pub readbuflen: size_t,
+    pub readpos: zend_off_t,
+    pub writepos: zend_off_t,
+    pub chunk_size: size_t,
+    pub enclosing_stream: *mut _php_stream,
+}
+impl _php_stream {
+    #[inline]
+    pub fn eof(&self) -> u8 {
+        (self._bitfield_1[0] >> 3) & 1
+    }
+    #[inline]
+    pub fn set_eof(&mut self, val: u8) {
+        self._bitfield_1[0] = (self._bitfield_1[0] & !(1 << 3)) | ((val & 1) << 3);
+    }
+}
+#[repr(C)]
+pub struct _php_stream_context {
+    pub notifier: *mut php_stream_notifier,
+    pub options: zval,
+    pub res: *mut zend_resource,
+}
+extern "C" {
+    pub fn _php_stream_read(
+        stream: *mut php_stream,
+        buf: *mut ::std::os::raw::c_char,
+        count: size_t,
+    ) -> ssize_t;
+}
+extern "C" {
+    pub fn _php_stream_write(
+        stream: *mut php_stream,
+        buf: *const ::std::os::raw::c_char,
+        count: size_t,
+    ) -> ssize_t;
+}
+extern "C" {
+    pub fn _php_stream_seek(
+        stream: *mut php_stream,
+        offset: zend_off_t,
+        whence: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn _php_stream_tell(stream: *mut php_stream) -> zend_off_t;
+}
+extern "C" {
+    pub fn _php_stream_flush(
+        stream: *mut php_stream,
+        closing: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn _php_stream_free(
+        stream: *mut php_stream,
+        close_options: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_file_le_stream() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_file_le_pstream() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_le_stream_context() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_stream_context_alloc() -> *mut php_stream_context;
+}
+extern "C" {
+    pub fn php_stream_context_get_option(
+        context: *mut php_stream_context,
+        wrappername: *const ::std::os::raw::c_char,
+        optionname: *const ::std::os::raw::c_char,
+    ) -> *mut zval;
+}
+extern "C" {
+    pub fn php_stream_context_set_option(
+        context: *mut php_stream_context,
+        wrappername: *const ::std::os::raw::c_char,
+        optionname: *const ::std::os::raw::c_char,
+        optionvalue: *mut zval,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn zend_list_delete(res: *mut zend_resource) -> zend_result;
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_open_wrapper(
+        path: *const ::std::os::raw::c_char,
+        mode: *const ::std::os::raw::c_char,
+        options: ::std::os::raw::c_int,
+        context: *mut php_stream_context,
+    ) -> *mut php_stream;
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_to_zval(stream: *mut php_stream, zv: *mut zval);
+}
+extern "C" {
+    pub fn php_register_url_stream_wrapper(
+        protocol: *const ::std::os::raw::c_char,
+        wrapper: *const php_stream_wrapper,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_unregister_url_stream_wrapper(
+        protocol: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn php_stream_wrapper_log_error(
+        wrapper: *const php_stream_wrapper,
+        options: ::std::os::raw::c_int,
+        fmt: *const ::std::os::raw::c_char,
+        ...
+    );
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_alloc(
+        ops: *const php_stream_ops,
+        abstract_: *mut ::std::os::raw::c_void,
+        mode: *const ::std::os::raw::c_char,
+    ) -> *mut php_stream;
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_statbuf_init(
+        ssb: *mut php_stream_statbuf,
+        mode: u32,
+        size: zend_off_t,
+        atime: i64,
+        mtime: i64,
+        ctime: i64,
+    );
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_filter_alloc(
+        fops: *const php_stream_filter_ops,
+        abstract_: *mut ::std::os::raw::c_void,
+        persistent: u8,
+    ) -> *mut php_stream_filter;
+}
+extern "C" {
+    pub fn ext_php_rs_php_stream_bucket_new(
+        stream: *mut php_stream,
+        buf: *const ::std::os::raw::c_char,
+        buflen: size_t,
+        persistent: u8,
+    ) -> *mut php_stream_bucket;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_llist_element {
+    pub next: *mut _zend_llist_element,
+    pub prev: *mut _zend_llist_element,
+    pub data: [::std::os::raw::c_char; 1usize],
+}
+pub type zend_llist_element = _zend_llist_element;
+pub type llist_dtor_func_t =
+    ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_llist {
+    pub head: *mut zend_llist_element,
+    pub tail: *mut zend_llist_element,
+    pub count: size_t,
+    pub size: size_t,
+    pub dtor: llist_dtor_func_t,
+    pub persistent: ::std::os::raw::c_uchar,
+    pub traverse_ptr: *mut zend_llist_element,
+}
+pub type zend_llist = _zend_llist;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_fcall_info_cache {
+    pub function_handler: *mut zend_function,
+    pub calling_scope: *mut zend_class_entry,
+    pub called_scope: *mut zend_class_entry,
+    pub object: *mut zend_object,
+}
+pub type zend_fcall_info_cache = _zend_fcall_info_cache;
+#[repr(C)]
+pub struct _zend_fcall_info {
+    pub size: size_t,
+    pub function_name: zval,
+    pub retval: *mut zval,
+    pub params: *mut zval,
+    pub object: *mut zend_object,
+    pub param_count: u32,
+    pub named_params: *mut HashTable,
+}
+pub type zend_fcall_info = _zend_fcall_info;
+extern "C" {
+    pub fn zend_call_function(
+        fci: *mut zend_fcall_info,
+        fci_cache: *mut zend_fcall_info_cache,
+    ) -> zend_result;
+}
+extern "C" {
+    pub fn zend_is_auto_global_str(name: *const ::std::os::raw::c_char, len: size_t) -> zend_bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapi_header_struct {
+    pub header: *mut ::std::os::raw::c_char,
+    pub header_len: size_t,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapi_headers_struct {
+    pub headers: zend_llist,
+    pub http_response_code: ::std::os::raw::c_int,
+    pub send_default_content_type: ::std::os::raw::c_uchar,
+    pub mime_type: *mut ::std::os::raw::c_char,
+    pub http_status_line: *mut ::std::os::raw::c_char,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _sapi_post_entry {
+    _unused: [u8; 0],
+}
+pub type sapi_post_entry = _sapi_post_entry;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapi_request_info {
+    pub request_method: *const ::std::os::raw::c_char,
+    pub query_string: *mut ::std::os::raw::c_char,
+    pub cookie_data: *mut ::std::os::raw::c_char,
+    pub content_length: zend_long,
+    pub path_translated: *mut ::std::os::raw::c_char,
+    pub request_uri: *mut ::std::os::raw::c_char,
+    pub request_body: *mut _php_stream,
+    pub content_type: *const ::std::os::raw::c_char,
+    pub headers_only: zend_bool,
+    pub no_headers: zend_bool,
+    pub headers_read: zend_bool,
+    pub post_entry: *mut sapi_post_entry,
+    pub content_type_dup: *mut ::std::os::raw::c_char,
+    pub auth_user: *mut ::std::os::raw::c_char,
+    pub auth_password: *mut ::std::os::raw::c_char,
+    pub auth_digest: *mut ::std::os::raw::c_char,
+    pub argv0: *mut ::std::os::raw::c_char,
+    pub current_user: *mut ::std::os::raw::c_char,
+    pub current_user_length: ::std::os::raw::c_int,
+    pub argc: ::std::os::raw::c_int,
+    pub argv: *mut *mut ::std::os::raw::c_char,
+    pub proto_num: ::std::os::raw::c_int,
+}
+#[repr(C)]
+pub struct _sapi_globals_struct {
+    pub server_context: *mut ::std::os::raw::c_void,
+    pub request_info: sapi_request_info,
+    pub sapi_headers: sapi_headers_struct,
+    pub read_post_bytes: i64,
+    pub post_read: ::std::os::raw::c_uchar,
+    pub headers_sent: ::std::os::raw::c_uchar,
+    pub global_stat: zend_stat_t,
+    pub default_mimetype: *mut ::std::os::raw::c_char,
+    pub default_charset: *mut ::std::os::raw::c_char,
+    pub rfc1867_uploaded_files: *mut HashTable,
+    pub post_max_size: zend_long,
+    pub options: ::std::os::raw::c_int,
+    pub sapi_started: zend_bool,
+    pub global_request_time: f64,
+    pub known_post_content_types: HashTable,
+    pub callback_func: zval,
+    pub fci_cache: zend_fcall_info_cache,
+}
+pub type sapi_globals_struct = _sapi_globals_struct;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapi_header_line {
+    pub line: *const ::std::os::raw::c_char,
+    pub line_len: size_t,
+    pub response_code: zend_long,
+}
+pub const sapi_header_op_enum_SAPI_HEADER_REPLACE: sapi_header_op_enum = 0;
+pub const sapi_header_op_enum_SAPI_HEADER_ADD: sapi_header_op_enum = 1;
+pub const sapi_header_op_enum_SAPI_HEADER_DELETE: sapi_header_op_enum = 2;
+pub const sapi_header_op_enum_SAPI_HEADER_DELETE_ALL: sapi_header_op_enum = 3;
+pub const sapi_header_op_enum_SAPI_HEADER_SET_STATUS: sapi_header_op_enum = 4;
+pub type sapi_header_op_enum = ::std::os::raw::c_uint;
+extern "C" {
+    pub fn sapi_header_op(
+        op: sapi_header_op_enum,
+        arg: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn ext_php_rs_sapi_globals() -> *mut sapi_globals_struct;
+}
+extern "C" {
+    pub fn ext_php_rs_zval_make_ref(zv: *mut zval);
+}
+extern "C" {
+    pub fn ext_php_rs_zval_persist(zv: *mut zval);
+}
+extern "C" {
+    pub fn ext_php_rs_zval_persist_release(zv: *mut zval);
+}
+extern "C" {
+    pub fn ext_php_rs_zval_separate(zv: *mut zval);
+}
+extern "C" {
+    pub fn ext_php_rs_pemalloc(size: size_t) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn ext_php_rs_pefree(ptr: *mut ::std::os::raw::c_void);
+}
+extern "C" {
+    pub fn ext_php_rs_info_print_box(content: *const ::std::os::raw::c_char);
+}
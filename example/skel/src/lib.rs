@@ -1,4 +1,7 @@
-use ext_php_rs::prelude::*;
+use ext_php_rs::{
+    php::{class::StaticContext, types::object::ClassObject},
+    prelude::*,
+};
 
 #[php_class]
 struct TestClass {
@@ -31,6 +34,10 @@ impl TestClass {
     fn set_test_name(&mut self, c: String) {
         self.c = c;
     }
+
+    fn create(ctx: StaticContext) -> PhpResult<ClassObject<'static, TestClass>> {
+        Ok(ctx.new_instance(TestClass::default())?)
+    }
 }
 
 #[php_module]
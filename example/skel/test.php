@@ -1,2 +1,12 @@
 <?php
 
+class TestSubClass extends TestClass
+{
+    const FOO = 'foo';
+
+    public $x = self::FOO;
+}
+
+$obj = TestSubClass::create();
+assert($obj instanceof TestSubClass);
+assert($obj->x === 'foo');
@@ -12,7 +12,9 @@ pub struct Class {
     pub class_name: String,
     pub struct_path: String,
     pub parent: Option<String>,
+    pub parent_field: Option<ParentField>,
     pub interfaces: Vec<String>,
+    pub debug_info: Option<DebugInfo>,
     pub methods: Vec<crate::method::Method>,
     pub constants: Vec<crate::constant::Constant>,
     pub properties: HashMap<String, Property>,
@@ -22,7 +24,25 @@ pub struct Class {
 pub enum ParsedAttribute {
     Extends(Expr),
     Implements(Expr),
+    DebugInfo(DebugInfo),
     Property(PropertyAttr),
+    Parent,
+}
+
+/// Field of a struct holding the registered class that the struct extends.
+#[derive(Debug)]
+pub struct ParentField {
+    pub field_name: String,
+    pub ty: String,
+}
+
+/// Source of the entries shown when an object is inspected from PHP.
+#[derive(Debug, Clone, Copy)]
+pub enum DebugInfo {
+    /// Uses the `Debug` implementation of the struct.
+    Fmt,
+    /// Uses the `PhpDebug` implementation of the struct.
+    Custom,
 }
 
 #[derive(Default, Debug, FromMeta)]
@@ -37,7 +57,9 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
 
     let mut parent = None;
     let mut interfaces = vec![];
+    let mut debug_info = None;
     let mut properties = HashMap::new();
+    let mut parent_field = None;
 
     input.attrs = {
         let mut unused = vec![];
@@ -50,6 +72,11 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
                     ParsedAttribute::Implements(class) => {
                         interfaces.push(class.to_token_stream().to_string());
                     }
+                    ParsedAttribute::DebugInfo(info) => {
+                        if debug_info.replace(info).is_some() {
+                            bail!("Only one `#[debug_info]` attribute may be used per class.");
+                        }
+                    }
                     attr => bail!("Attribute `{:?}` is not valid for structs.", attr),
                 },
                 None => unused.push(attr),
@@ -63,7 +90,7 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
         named,
     }) = &mut input.fields
     {
-        for field in named.iter_mut() {
+        for (i, field) in named.iter_mut().enumerate() {
             let mut attrs = vec![];
             attrs.append(&mut field.attrs);
             for attr in attrs.into_iter() {
@@ -84,6 +111,24 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
                                 ),
                             );
                         }
+                        ParsedAttribute::Parent => {
+                            let field_name = field
+                                .ident
+                                .as_ref()
+                                .ok_or_else(|| anyhow!("Only named fields can be parents."))?
+                                .to_string();
+
+                            // The parent must be placed at the start of the struct, so that the
+                            // object can be used wherever the parent is expected.
+                            if i != 0 {
+                                bail!("The `#[parent]` field `{}` must be the first field of the struct.", field_name);
+                            }
+
+                            parent_field = Some(ParentField {
+                                field_name,
+                                ty: field.ty.to_token_stream().to_string(),
+                            });
+                        }
                         _ => bail!("Attribute {:?} is not valid for struct fields.", attr),
                     },
                     None => field.attrs.push(attr),
@@ -92,6 +137,21 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
         }
     }
 
+    if let Some(field) = &parent_field {
+        if parent.is_some() {
+            bail!("The `#[extends]` attribute cannot be used together with a `#[parent]` field.");
+        }
+
+        parent = Some(format!(
+            "<{} as ::ext_php_rs::php::types::object::RegisteredClass>::get_metadata().ce()",
+            field.ty
+        ));
+
+        if !input.attrs.iter().any(|attr| attr.path.is_ident("repr")) {
+            input.attrs.push(syn::parse_quote! { #[repr(C)] });
+        }
+    }
+
     let ItemStruct { ident, .. } = &input;
     let class_name = args.name.unwrap_or_else(|| ident.to_string());
     let struct_path = ident.to_string();
@@ -99,7 +159,9 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
         class_name,
         struct_path,
         parent,
+        parent_field,
         interfaces,
+        debug_info,
         properties,
         ..Default::default()
     };
@@ -264,6 +326,25 @@ fn parse_attribute(attr: &Attribute) -> Result<Option<ParsedAttribute>> {
                 .map_err(|_| anyhow!("Unable to parse `#[{}]` attribute.", name))?;
             Some(ParsedAttribute::Implements(meta))
         }
+        "debug_info" => {
+            let info = if attr.tokens.is_empty() {
+                DebugInfo::Fmt
+            } else {
+                let ident: Ident = attr
+                    .parse_args()
+                    .map_err(|_| anyhow!("Unable to parse `#[{}]` attribute.", name))?;
+
+                match ident.to_string().as_str() {
+                    "fmt" => DebugInfo::Fmt,
+                    "custom" => DebugInfo::Custom,
+                    _ => bail!(
+                        "Invalid argument given for `#[{}]` attribute, expected `fmt` or `custom`.",
+                        name
+                    ),
+                }
+            };
+            Some(ParsedAttribute::DebugInfo(info))
+        }
         "prop" | "property" => {
             let attr = if attr.tokens.is_empty() {
                 PropertyAttr::default()
@@ -274,6 +355,7 @@ fn parse_attribute(attr: &Attribute) -> Result<Option<ParsedAttribute>> {
 
             Some(ParsedAttribute::Property(attr))
         }
+        "parent" => Some(ParsedAttribute::Parent),
         _ => None,
     })
 }
@@ -1,8 +1,8 @@
 use anyhow::{bail, Result};
 use darling::ToTokens;
-use proc_macro2::{Ident, Literal, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
-use syn::ItemConst;
+use syn::{Expr, ItemConst, Type};
 
 use crate::STATE;
 
@@ -10,7 +10,9 @@ use crate::STATE;
 pub struct Constant {
     pub name: String,
     // pub visibility: Visibility,
+    pub ty: String,
     pub value: String,
+    pub deprecated: bool,
 }
 
 pub fn parser(input: ItemConst) -> Result<TokenStream> {
@@ -22,7 +24,12 @@ pub fn parser(input: ItemConst) -> Result<TokenStream> {
 
     state.constants.push(Constant {
         name: input.ident.to_string(),
+        ty: input.ty.to_token_stream().to_string(),
         value: input.expr.to_token_stream().to_string(),
+        deprecated: input
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("deprecated")),
     });
 
     Ok(quote! {
@@ -32,11 +39,21 @@ pub fn parser(input: ItemConst) -> Result<TokenStream> {
 }
 
 impl Constant {
+    /// Returns an expression evaluating to the value of the constant, with the type of the
+    /// constant.
     pub fn val_tokens(&self) -> TokenStream {
-        syn::parse_str::<Literal>(&self.value)
-            .map(|lit| lit.to_token_stream())
-            .or_else(|_| syn::parse_str::<Ident>(&self.value).map(|ident| ident.to_token_stream()))
-            .unwrap_or(quote! { Default::default() })
+        match (
+            syn::parse_str::<Type>(&self.ty),
+            syn::parse_str::<Expr>(&self.value),
+        ) {
+            (Ok(ty), Ok(expr)) => quote! {
+                {
+                    let val: #ty = #expr;
+                    val
+                }
+            },
+            _ => quote! { Default::default() },
+        }
     }
 
     // pub fn get_flags(&self) -> TokenStream {
@@ -25,6 +25,18 @@ pub struct Arg {
     pub ty: String,
     pub nullable: bool,
     pub default: Option<String>,
+    pub as_ref: bool,
+    pub variadic: bool,
+}
+
+/// How the values given to a variadic parameter are collected.
+enum VariadicKind {
+    /// Converted into a `Vec` of the given type.
+    Values(Box<Type>),
+    /// Borrowed as a slice of zvals.
+    Zvals,
+    /// Collected into a map of positional and named values.
+    Map,
 }
 
 #[derive(Debug, Clone)]
@@ -36,11 +48,12 @@ pub struct Function {
     pub output: Option<(String, bool)>,
 }
 
-pub fn parser(args: AttributeArgs, input: ItemFn) -> Result<(TokenStream, Function)> {
+pub fn parser(args: AttributeArgs, mut input: ItemFn) -> Result<(TokenStream, Function)> {
     let attr_args = match AttrArgs::from_list(&args) {
         Ok(args) => args,
         Err(e) => bail!("Unable to parse attribute arguments: {:?}", e),
     };
+    let variadic = take_variadic_attr(&mut input.sig.inputs)?;
 
     let ItemFn { sig, .. } = &input;
     let Signature {
@@ -54,7 +67,12 @@ pub fn parser(args: AttributeArgs, input: ItemFn) -> Result<(TokenStream, Functi
         &format!("_internal_php_{}", ident.to_string()),
         Span::call_site(),
     );
-    let args = build_args(inputs, &attr_args.defaults)?;
+    let mut args = build_args(inputs, &attr_args.defaults)?;
+    if variadic {
+        if let Some(arg) = args.last_mut() {
+            arg.set_variadic()?;
+        }
+    }
     let optional = find_optional_parameter(args.iter(), attr_args.optional);
     let arg_definitions = build_arg_definitions(&args);
     let arg_parser = build_arg_parser(args.iter(), &optional)?;
@@ -122,6 +140,29 @@ fn build_args(
         .collect::<Result<Vec<_>>>()
 }
 
+/// Removes the `#[variadic]` attributes from the parameters of a function, returning whether the
+/// last parameter was marked as variadic.
+pub fn take_variadic_attr(inputs: &mut Punctuated<FnArg, Token![,]>) -> Result<bool> {
+    let len = inputs.len();
+    let mut variadic = false;
+
+    for (i, arg) in inputs.iter_mut().enumerate() {
+        if let FnArg::Typed(ty) = arg {
+            let before = ty.attrs.len();
+            ty.attrs.retain(|attr| !attr.path.is_ident("variadic"));
+
+            if ty.attrs.len() != before {
+                if i + 1 != len {
+                    bail!("Only the last parameter of a function can be variadic.");
+                }
+                variadic = true;
+            }
+        }
+    }
+
+    Ok(variadic)
+}
+
 fn build_arg_definitions(args: &[Arg]) -> Vec<TokenStream> {
     args.iter()
         .map(|ty| {
@@ -144,7 +185,8 @@ pub fn find_optional_parameter<'a>(
 
     let mut optional = None;
 
-    for arg in args.rev() {
+    // A variadic parameter is always optional, and is not required to be nullable.
+    for arg in args.rev().skip_while(|arg| arg.variadic) {
         if arg.nullable {
             optional.replace(arg.name.clone());
         } else {
@@ -175,7 +217,7 @@ pub fn build_arg_parser<'a>(
                 quote! {}
             };
 
-            if rest_optional && !arg.nullable && arg.default.is_none() {
+            if rest_optional && !arg.nullable && arg.default.is_none() && !arg.variadic {
                 bail!(
                     "Parameter `{}` must be a variant of `Option` or have a default value as it is optional.",
                     arg.name
@@ -214,15 +256,69 @@ pub fn get_return_type(output_type: &ReturnType) -> Result<Option<(String, bool)
 }
 
 impl Arg {
-    pub fn new(name: &str, ty: &str, nullable: bool, default: Option<String>) -> Self {
+    pub fn new(
+        name: &str,
+        ty: &str,
+        nullable: bool,
+        default: Option<String>,
+        as_ref: bool,
+    ) -> Self {
         Self {
             name: name.to_string(),
-            ty: Regex::new(r"'[A-Za-z]+")
+            ty: Regex::new(r"'[A-Za-z_]+\s*,?")
                 .unwrap()
                 .replace_all(ty, "")
                 .to_string(),
             nullable,
             default,
+            as_ref,
+            variadic: false,
+        }
+    }
+
+    /// Marks the parameter as variadic, collecting the remaining arguments given to the function.
+    /// The parameter must either be a `Vec<T>`, a `&[&Zval]` or a `HashMap<String, Zval>`.
+    pub fn set_variadic(&mut self) -> Result<()> {
+        if self.nullable || self.as_ref || self.variadic_kind().is_none() {
+            bail!(
+                "Variadic parameter `{}` must be of type `Vec<T>`, `&[&Zval]` or `HashMap<String, Zval>`.",
+                self.name
+            );
+        }
+
+        self.variadic = true;
+        Ok(())
+    }
+
+    /// Returns how the values of a variadic parameter are collected, or `None` if the type is not
+    /// valid for variadic parameters.
+    fn variadic_kind(&self) -> Option<VariadicKind> {
+        match syn::parse_str::<Type>(&self.ty).ok()? {
+            Type::Path(TypePath { path, .. }) => {
+                let seg = path.segments.last()?;
+                if seg.ident == "HashMap" {
+                    return Some(VariadicKind::Map);
+                }
+                if seg.ident != "Vec" {
+                    return None;
+                }
+
+                match &seg.arguments {
+                    PathArguments::AngleBracketed(args) => {
+                        args.args.iter().find_map(|arg| match arg {
+                            GenericArgument::Type(ty) => {
+                                Some(VariadicKind::Values(Box::new(ty.clone())))
+                            }
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            Type::Reference(ref_) if matches!(&*ref_.elem, Type::Slice(_)) => {
+                Some(VariadicKind::Zvals)
+            }
+            _ => None,
         }
     }
 
@@ -258,15 +354,22 @@ impl Arg {
                     &stringified,
                     seg.ident == "Option" || default.is_some(),
                     default,
+                    seg.ident == "ZendRef",
                 ))
             }
             Type::Reference(ref_) => {
+                // `&mut Zval` parameters are passed by reference.
+                let as_ref = ref_.mutability.is_some()
+                    && matches!(&*ref_.elem, Type::Path(TypePath { path, .. })
+                        if path.segments.last().map(|seg| seg.ident == "Zval").unwrap_or(false));
+
                 // Returning references is invalid, so let's just create our arg
                 Some(Arg::new(
                     name,
                     &ref_.to_token_stream().to_string(),
                     false,
                     default,
+                    as_ref,
                 ))
             }
             _ => None,
@@ -275,9 +378,24 @@ impl Arg {
 
     #[inline]
     pub fn get_type_ident(&self) -> TokenStream {
+        // The type of a variadic parameter is the type of each of the arguments it collects.
+        if self.variadic {
+            return match self.variadic_kind() {
+                Some(VariadicKind::Values(ty)) => {
+                    quote! { <#ty as ::ext_php_rs::php::types::zval::FromZval>::type_spec() }
+                }
+                _ => quote! { ::ext_php_rs::php::enums::DataType::Mixed },
+            };
+        }
+
+        // Parameters passed by reference accept a value of any type.
+        if self.as_ref {
+            return quote! { ::ext_php_rs::php::enums::DataType::Mixed };
+        }
+
         let ty: Type = syn::parse_str(&self.ty).unwrap();
         quote! {
-            <#ty as ::ext_php_rs::php::types::zval::FromZval>::TYPE
+            <#ty as ::ext_php_rs::php::types::zval::FromZval>::type_spec()
         }
     }
 
@@ -291,6 +409,67 @@ impl Arg {
         let name = &self.name;
         let name_ident = self.get_name_ident();
 
+        if self.variadic {
+            return match self.variadic_kind() {
+                Some(VariadicKind::Values(_)) => quote! {
+                    match #name_ident.variadic_vals() {
+                        Ok(vals) => vals,
+                        Err(::ext_php_rs::errors::Error::InvalidArgument(position, _)) => {
+                            #name_ident.throw_variadic_type_error(position);
+                            return;
+                        }
+                        Err(e) => {
+                            let e: ::ext_php_rs::php::exceptions::PhpException = e.into();
+                            e.throw().expect(concat!("Failed to throw exception: Invalid value given for argument `", #name, "`."));
+                            return;
+                        }
+                    }
+                },
+                Some(VariadicKind::Map) => quote! { #name_ident.variadic_map() },
+                _ => quote! { #name_ident.variadic_zvals() },
+            };
+        }
+
+        if self.as_ref {
+            // `&mut Zval` parameters receive the referenced zval, other parameters passed by
+            // reference are `ZendRef`s.
+            let (ref_ty, into_zval) = if self.ty.starts_with('&') {
+                (
+                    quote! { ::ext_php_rs::php::types::reference::ZendRef::<()> },
+                    Some(
+                        quote! { .map(::ext_php_rs::php::types::reference::ZendRef::into_zval_mut) },
+                    ),
+                )
+            } else {
+                (
+                    quote! { ::ext_php_rs::php::types::reference::ZendRef },
+                    None,
+                )
+            };
+
+            return quote! {
+                // SAFETY: The reference is only retrieved once, and the argument is not accessed
+                // otherwise.
+                match #name_ident.zval().and_then(|zv| unsafe {
+                    #ref_ty::new(zv)
+                })#into_zval {
+                    Some(val) => val,
+                    None => {
+                        #name_ident.throw_type_error();
+                        return;
+                    }
+                }
+            };
+        }
+
+        // Scalar values are coerced following the `strict_types` mode of the caller.
+        let coerced = self.is_coerced();
+        let getter = if coerced {
+            quote! { coerced_val }
+        } else {
+            quote! { val }
+        };
+
         if let Some(default) = self.default.as_ref() {
             // `bool`s are not literals - need to use Ident.
             let val = syn::parse_str::<Literal>(default)
@@ -298,19 +477,33 @@ impl Arg {
                 .or_else(|_| Ident::from_string(default).map(|ident| ident.to_token_stream()))
                 .unwrap_or(quote! { Default::default() });
 
-            quote! { #name_ident.val().unwrap_or(#val.into()) }
+            // The default is used when the argument is not given or is null, otherwise a value of
+            // the wrong type is an error.
+            quote! {
+                match #name_ident.zval() {
+                    Some(zv) if !zv.is_null() => match #name_ident.#getter() {
+                        Some(val) => val,
+                        None => {
+                            #name_ident.throw_type_error();
+                            return;
+                        }
+                    },
+                    _ => #val.into(),
+                }
+            }
         } else if self.nullable {
-            quote! { #name_ident.val() }
+            if coerced {
+                // `null` is converted into `None` by the `Option`, rather than being coerced.
+                quote! { #name_ident.coerced_val().flatten() }
+            } else {
+                quote! { #name_ident.val() }
+            }
         } else {
             quote! {
-                match #name_ident.val() {
+                match #name_ident.#getter() {
                     Some(val) => val,
                     None => {
-                        ::ext_php_rs::php::exceptions::PhpException::default(
-                            concat!("Invalid value given for argument `", #name, "`.").into()
-                        )
-                        .throw()
-                        .expect(concat!("Failed to throw exception: Invalid value given for argument `", #name, "`."));
+                        #name_ident.throw_type_error();
                         return;
                     }
                 }
@@ -318,20 +511,81 @@ impl Arg {
         }
     }
 
+    /// Returns whether the value of the parameter is coerced from other types, which is the case
+    /// for the scalar types implementing `FromZvalCoerced`, and `Option`s of them.
+    fn is_coerced(&self) -> bool {
+        const COERCED: &[&str] = &[
+            "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "usize", "isize", "f32", "f64",
+            "bool", "String",
+        ];
+
+        fn is_coerced(ty: &Type) -> bool {
+            match ty {
+                Type::Path(TypePath { path, .. }) => match path.segments.last() {
+                    Some(seg) if seg.ident == "Option" => match &seg.arguments {
+                        PathArguments::AngleBracketed(args) => {
+                            matches!(args.args.first(), Some(GenericArgument::Type(ty)) if is_coerced(ty))
+                        }
+                        _ => false,
+                    },
+                    Some(seg) => {
+                        seg.arguments.is_empty() && COERCED.iter().any(|ty| seg.ident == ty)
+                    }
+                    None => false,
+                },
+                Type::Reference(ref_) => {
+                    ref_.mutability.is_none()
+                        && matches!(&*ref_.elem, Type::Path(TypePath { path, .. }) if path.is_ident("str"))
+                }
+                _ => false,
+            }
+        }
+
+        syn::parse_str::<Type>(&self.ty)
+            .map(|ty| is_coerced(&ty))
+            .unwrap_or(false)
+    }
+
+    /// Returns the default value of the parameter as a PHP expression, or `None` if the parameter
+    /// has no default value or the value cannot be represented in PHP.
+    fn php_default(&self) -> Option<String> {
+        let quote_str = |val: &str| format!("'{}'", val.replace('\\', "\\\\").replace('\'', "\\'"));
+
+        Some(match syn::parse_str::<Lit>(self.default.as_ref()?).ok()? {
+            Lit::Str(val) => quote_str(&val.value()),
+            Lit::Char(val) => quote_str(&val.value().to_string()),
+            // Integer literals with a float suffix, such as `2f64`, are floats.
+            Lit::Int(val) if val.suffix().starts_with('f') => format!("{}.0", val.base10_digits()),
+            Lit::Int(val) => val.base10_digits().to_string(),
+            Lit::Float(val) => {
+                let digits = val.base10_digits();
+                if digits.contains(&['.', 'e', 'E'][..]) {
+                    digits.to_string()
+                } else {
+                    format!("{}.0", digits)
+                }
+            }
+            Lit::Bool(val) => val.value.to_string(),
+            _ => return None,
+        })
+    }
+
     /// Returns a [`TokenStream`] containing the line required to instantiate the argument.
     pub fn get_arg_definition(&self) -> TokenStream {
         let name = &self.name;
         let ty = self.get_type_ident();
 
         let null = self.nullable.then(|| quote! { .allow_null() });
-        let default = self.default.as_ref().map(|val| {
+        let as_ref = self.as_ref.then(|| quote! { .as_ref() });
+        let variadic = self.variadic.then(|| quote! { .is_variadic() });
+        let default = self.php_default().map(|val| {
             quote! {
                 .default(#val)
             }
         });
 
         quote! {
-            ::ext_php_rs::php::args::Arg::new(#name, #ty) #null #default
+            ::ext_php_rs::php::args::Arg::new(#name, #ty) #null #default #as_ref #variadic
         }
     }
 }
@@ -365,7 +619,7 @@ impl Function {
 
             // TODO allow reference returns?
             quote! {
-                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE, false, #nullable)
+                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::type_spec(), false, #nullable)
             }
         });
 
@@ -377,3 +631,26 @@ impl Function {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Arg;
+
+    #[test]
+    fn test_php_default() {
+        for &(default, expected) in &[
+            ("\"hello\"", Some("'hello'")),
+            ("\"it's a \\\\ test\"", Some("'it\\'s a \\\\ test'")),
+            ("'c'", Some("'c'")),
+            ("5", Some("5")),
+            ("5i64", Some("5")),
+            ("1.5", Some("1.5")),
+            ("2f64", Some("2.0")),
+            ("true", Some("true")),
+            ("b\"bytes\"", None),
+        ] {
+            let arg = Arg::new("arg", "i64", true, Some(default.to_string()), false);
+            assert_eq!(expected.map(String::from), arg.php_default());
+        }
+    }
+}
@@ -129,7 +129,9 @@ pub fn parser(args: AttributeArgs, input: ItemImpl) -> Result<TokenStream> {
                     class.constants.push(Constant {
                         name: constant.ident.to_string(),
                         // visibility: Visibility::Public,
+                        ty: constant.ty.to_token_stream().to_string(),
                         value: constant.expr.to_token_stream().to_string(),
+                        deprecated: false,
                     });
 
                     quote! {
@@ -13,6 +13,8 @@ use syn::{punctuated::Punctuated, FnArg, ImplItemMethod, Lit, Pat, Signature, To
 #[derive(Debug, Clone)]
 pub enum Arg {
     Receiver(bool),
+    /// A `StaticContext` parameter, given the name of the parameter.
+    Context(String),
     Typed(function::Arg),
 }
 
@@ -93,6 +95,7 @@ pub fn parser(input: &mut ImplItemMethod, rename_rule: RenameRule) -> Result<Par
     }
 
     input.attrs.clear();
+    let variadic = function::take_variadic_attr(&mut input.sig.inputs)?;
 
     let ImplItemMethod { sig, .. } = &input;
     let Signature {
@@ -103,7 +106,12 @@ pub fn parser(input: &mut ImplItemMethod, rename_rule: RenameRule) -> Result<Par
     } = &sig;
 
     let internal_ident = Ident::new(&format!("_internal_php_{}", ident), Span::call_site());
-    let args = build_args(inputs, &defaults)?;
+    let mut args = build_args(inputs, &defaults)?;
+    if variadic {
+        if let Some(Arg::Typed(arg)) = args.last_mut() {
+            arg.set_variadic()?;
+        }
+    }
     let optional = function::find_optional_parameter(
         args.iter().filter_map(|arg| match arg {
             Arg::Typed(arg) => Some(arg),
@@ -172,6 +180,9 @@ fn build_args(
                     Pat::Ident(pat) => pat.ident.to_string(),
                     _ => bail!("Invalid parameter type."),
                 };
+                if is_static_context(&ty.ty) {
+                    return Ok(Arg::Context(name));
+                }
                 Ok(Arg::Typed(
                     crate::function::Arg::from_type(&name, &ty.ty, defaults.get(&name), false)
                         .ok_or_else(|| anyhow!("Invalid parameter type for `{}`.", name))?,
@@ -181,6 +192,20 @@ fn build_args(
         .collect()
 }
 
+/// Checks whether the type of a parameter is `StaticContext`, which is filled in from the
+/// execution data rather than passed from PHP.
+fn is_static_context(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident == "StaticContext")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 fn build_arg_definitions(args: &[Arg]) -> (Vec<TokenStream>, bool) {
     let mut _static = true;
 
@@ -201,6 +226,19 @@ fn build_arg_definitions(args: &[Arg]) -> (Vec<TokenStream>, bool) {
                     };
                 }
             }
+            Arg::Context(name) => {
+                let ident = Ident::new(name, Span::call_site());
+
+                quote! {
+                    let #ident = match ::ext_php_rs::php::class::StaticContext::new(ex) {
+                        Some(ctx) => ctx,
+                        None => return ::ext_php_rs::php::exceptions::throw(
+                            ::ext_php_rs::php::class::ClassEntry::exception(),
+                            "Failed to retrieve the class the method was called on."
+                        ).expect("Failed to throw exception: Failed to retrieve the class the method was called on."),
+                    };
+                }
+            }
             Arg::Typed(arg) => {
                 let ident = arg.get_name_ident();
                 let definition = arg.get_arg_definition();
@@ -228,6 +266,7 @@ fn build_arg_parser<'a>(
 fn build_arg_accessors(args: &[Arg]) -> Vec<TokenStream> {
     args.iter()
         .filter_map(|arg| match arg {
+            Arg::Context(name) => Some(Ident::new(name, Span::call_site()).to_token_stream()),
             Arg::Typed(arg) => Some(arg.get_accessor()),
             _ => None,
         })
@@ -266,7 +305,7 @@ impl Method {
 
             // TODO allow reference returns?
             quote! {
-                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE, false, #nullable)
+                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::type_spec(), false, #nullable)
             }
         });
 
@@ -1,11 +1,30 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, bail, Result};
+use darling::FromMeta;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{ItemFn, Signature};
-
-use crate::{class::Class, startup_function, STATE};
+use syn::{AttributeArgs, ItemFn, Signature};
+
+use crate::{
+    class::{Class, DebugInfo},
+    startup_function, STATE,
+};
+
+#[derive(Default, Debug, FromMeta)]
+#[darling(default)]
+pub struct AttrArgs {
+    #[darling(multiple)]
+    requires: Vec<String>,
+    #[darling(multiple)]
+    optional: Vec<String>,
+    #[darling(multiple)]
+    conflicts: Vec<String>,
+}
 
-pub fn parser(input: ItemFn) -> Result<TokenStream> {
+pub fn parser(args: AttributeArgs, input: ItemFn) -> Result<TokenStream> {
+    let args = AttrArgs::from_list(&args)
+        .map_err(|e| anyhow!("Unable to parse attribute arguments: {:?}", e))?;
     let ItemFn { sig, block, .. } = input;
     let Signature { output, inputs, .. } = sig;
     let stmts = &block.stmts;
@@ -47,6 +66,22 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
             .startup_function(#ident)
         }
     });
+    let dependency_type = quote! { ::ext_php_rs::php::module::DependencyType };
+    let dependencies = args
+        .requires
+        .iter()
+        .map(|name| quote! { .dependency(#name, #dependency_type::Required) })
+        .chain(
+            args.optional
+                .iter()
+                .map(|name| quote! { .dependency(#name, #dependency_type::Optional) }),
+        )
+        .chain(
+            args.conflicts
+                .iter()
+                .map(|name| quote! { .dependency(#name, #dependency_type::Conflicts) }),
+        );
+    let info_fn = generate_info_function(&state.classes);
     let registered_classes_impls = state
         .classes
         .iter()
@@ -58,6 +93,8 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
 
         #startup_fn
 
+        #info_fn
+
         #[doc(hidden)]
         #[no_mangle]
         pub extern "C" fn get_module() -> *mut ::ext_php_rs::php::module::ModuleEntry {
@@ -69,6 +106,8 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
                 env!("CARGO_PKG_NAME"),
                 env!("CARGO_PKG_VERSION")
             )
+            .info_function(ext_php_rs_module_info)
+            #(#dependencies)*
             #startup
             #(.function(#functions.unwrap()))*
             ;
@@ -85,6 +124,36 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
     Ok(result)
 }
 
+/// Generates the default information function of the module, displaying the version of the
+/// extension, how PHP was built and the classes registered by the extension.
+fn generate_info_function(classes: &HashMap<String, Class>) -> TokenStream {
+    let mut class_names = classes
+        .values()
+        .map(|class| class.class_name.as_str())
+        .collect::<Vec<_>>();
+    class_names.sort_unstable();
+    let classes_row = if class_names.is_empty() {
+        None
+    } else {
+        let class_names = class_names.join(", ");
+        Some(quote! { .row(["Classes", #class_names]) })
+    };
+
+    quote! {
+        #[doc(hidden)]
+        pub extern "C" fn ext_php_rs_module_info(module: *mut ::ext_php_rs::php::module::ModuleEntry) {
+            if let Some(module) = unsafe { module.as_ref() } {
+                let _ = ::ext_php_rs::php::info::InfoTable::new()
+                    .header([concat!(env!("CARGO_PKG_NAME"), " support"), "enabled"])
+                    .row(["Version", env!("CARGO_PKG_VERSION")])
+                    .build_info()
+                    #classes_row
+                    .display(module);
+            }
+        }
+    }
+}
+
 /// Generates an implementation for `RegisteredClass` on the given class.
 pub fn generate_registered_class_impl(class: &Class) -> Result<TokenStream> {
     let self_ty = Ident::new(&class.struct_path, Span::call_site());
@@ -94,6 +163,32 @@ pub fn generate_registered_class_impl(class: &Class) -> Result<TokenStream> {
         .properties
         .iter()
         .map(|(name, prop)| prop.as_prop_tuple(name));
+    let parent_props = match &class.parent_field {
+        Some(field) => {
+            let field_name = Ident::new(&field.field_name, Span::call_site());
+            let ty: syn::Type = syn::parse_str(&field.ty)
+                .map_err(|_| anyhow!("Invalid type given for `{}` parent", class_name))?;
+
+            Some(quote! {
+                for (name, prop) in <#ty as ::ext_php_rs::php::types::object::RegisteredClass>::get_properties() {
+                    props.entry(name).or_insert_with(|| prop.inherit(|obj: &Self| &obj.#field_name, |obj: &mut Self| &mut obj.#field_name));
+                }
+            })
+        }
+        None => None,
+    };
+    let debug_info = class.debug_info.map(|info| {
+        let info = match info {
+            DebugInfo::Fmt => quote! { ::ext_php_rs::php::types::object::debug_info_from_fmt(self) },
+            DebugInfo::Custom => quote! { ::ext_php_rs::php::types::object::PhpDebug::debug_info(self) },
+        };
+
+        quote! {
+            fn get_debug_info(&self) -> Option<Vec<(String, ::ext_php_rs::php::types::zval::Zval)>> {
+                Some(#info)
+            }
+        }
+    });
 
     Ok(quote! {
         static #meta: ::ext_php_rs::php::types::object::ClassMetadata<#self_ty> = ::ext_php_rs::php::types::object::ClassMetadata::new();
@@ -108,10 +203,17 @@ pub fn generate_registered_class_impl(class: &Class) -> Result<TokenStream> {
             fn get_properties<'a>() -> ::std::collections::HashMap<&'static str, ::ext_php_rs::php::types::props::Property<'a, Self>> {
                 use ::std::iter::FromIterator;
 
-                ::std::collections::HashMap::from_iter([
+                #[allow(unused_mut)]
+                let mut props = ::std::collections::HashMap::from_iter([
                     #(#prop_tuples)*
-                ])
+                ]);
+
+                #parent_props
+
+                props
             }
+
+            #debug_info
         }
     })
 }
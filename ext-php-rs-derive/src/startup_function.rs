@@ -45,8 +45,8 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
 
 /// Returns a vector of `ClassBuilder`s for each class.
 fn build_classes(classes: &HashMap<String, Class>) -> Result<Vec<TokenStream>> {
-    classes
-        .iter()
+    sort_classes(classes)
+        .into_iter()
         .map(|(name, class)| {
             let Class { class_name, .. } = &class;
             let ident = Ident::new(name, Span::call_site());
@@ -132,14 +132,55 @@ fn build_classes(classes: &HashMap<String, Class>) -> Result<Vec<TokenStream>> {
         .collect::<Result<Vec<_>>>()
 }
 
+/// Orders the classes so that each class is registered after the registered class it holds as
+/// its `#[parent]`, as the class entry of the parent is required to build the class.
+fn sort_classes(classes: &HashMap<String, Class>) -> Vec<(&String, &Class)> {
+    let parent_of = |class: &Class| {
+        class
+            .parent_field
+            .as_ref()
+            .and_then(|field| field.ty.rsplit("::").next())
+            .map(|ty| ty.trim().to_string())
+    };
+
+    let mut remaining: Vec<_> = classes.iter().collect();
+    let mut sorted = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .position(|(_, class)| match parent_of(class) {
+                Some(parent) => !remaining.iter().any(|(name, _)| **name == parent),
+                None => true,
+            })
+            .unwrap_or(0);
+        sorted.push(remaining.remove(next));
+    }
+
+    sorted
+}
+
 fn build_constants(constants: &[Constant]) -> Vec<TokenStream> {
     constants
         .iter()
         .map(|constant| {
             let name = &constant.name;
             let val = constant.val_tokens();
-            quote! {
-                #val.register_constant(#name, module_number).unwrap();
+            if constant.deprecated {
+                quote! {
+                    #val.register_constant_flags(
+                        #name,
+                        module_number,
+                        ::ext_php_rs::php::flags::GlobalConstantFlags::CaseSensitive
+                            | ::ext_php_rs::php::flags::GlobalConstantFlags::Persistent
+                            | ::ext_php_rs::php::flags::GlobalConstantFlags::Deprecated,
+                    )
+                    .unwrap();
+                }
+            } else {
+                quote! {
+                    #val.register_constant(#name, module_number).unwrap();
+                }
             }
         })
         .collect()
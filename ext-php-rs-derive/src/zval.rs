@@ -0,0 +1,73 @@
+use anyhow::{anyhow, bail, Result};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericParam, Lifetime};
+
+pub fn parser(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => bail!("`FromZval` can only be derived for enums."),
+    };
+
+    // The lifetime of the enum is used as the lifetime of the zval, so that variants can borrow
+    // from it.
+    let mut lifetimes = input.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(def) => Ok(def.lifetime.clone()),
+        _ => Err(anyhow!(
+            "`FromZval` cannot be derived for enums with type or const parameters."
+        )),
+    });
+    let (lifetime, generics) = match lifetimes.next() {
+        Some(lifetime) => {
+            let lifetime = lifetime?;
+            (lifetime.clone(), quote! { <#lifetime> })
+        }
+        None => (Lifetime::new("'zval", Span::call_site()), quote! {}),
+    };
+    if lifetimes.next().is_some() {
+        bail!("`FromZval` cannot be derived for enums with more than one lifetime.");
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Ok((&variant.ident, &fields.unnamed[0].ty))
+            }
+            _ => Err(anyhow!(
+                "Variant `{}` must contain a single unnamed field to derive `FromZval`.",
+                variant.ident
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let type_specs = variants.iter().map(|(_, ty)| {
+        quote! {
+            <#ty as ::ext_php_rs::php::types::zval::FromZval>::type_spec()
+        }
+    });
+    let conversions = variants.iter().map(|(variant, ty)| {
+        quote! {
+            if let Some(val) = <#ty as ::ext_php_rs::php::types::zval::FromZval>::from_zval(zval) {
+                return Some(Self::#variant(val));
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl<#lifetime> ::ext_php_rs::php::types::zval::FromZval<#lifetime> for #ident #generics {
+            const TYPE: ::ext_php_rs::php::enums::DataType = ::ext_php_rs::php::enums::DataType::Mixed;
+
+            fn type_spec() -> ::ext_php_rs::php::types::type_spec::TypeSpec {
+                ::ext_php_rs::php::types::type_spec::TypeSpec::union(vec![#(#type_specs),*])
+            }
+
+            fn from_zval(zval: &#lifetime ::ext_php_rs::php::types::zval::Zval) -> Option<Self> {
+                #(#conversions)*
+                None
+            }
+        }
+    })
+}
@@ -3,6 +3,7 @@
 use std::{error::Error as ErrorTrait, ffi::NulError, fmt::Display};
 
 use crate::php::{
+    class::ClassEntry,
     enums::DataType,
     exceptions::PhpException,
     flags::{ClassFlags, ZvalTypeFlags},
@@ -50,6 +51,54 @@ pub enum Error {
     InvalidException(ClassFlags),
     /// Converting integer arguments resulted in an overflow.
     IntegerOverflow,
+    /// An argument given to a function could not be converted into the required type.
+    ///
+    /// The enum carries the position of the argument, starting from 1, and the data type of
+    /// the given value.
+    InvalidArgument(usize, DataType),
+    /// A named argument was given to a function which does not match any of its parameters.
+    ///
+    /// The enum carries the name of the argument.
+    UnknownNamedParameter(String),
+    /// A named argument was given for a parameter which was already given positionally.
+    ///
+    /// The enum carries the name of the argument.
+    DuplicateNamedParameter(String),
+    /// A type declaration is not supported by the version of PHP in use.
+    ///
+    /// The enum carries the type declaration.
+    UnsupportedType(String),
+    /// An object could not be created from a class, as the class is abstract, an interface or a
+    /// trait.
+    ///
+    /// The enum carries the name of the class.
+    Instantiation(String),
+    /// An operation on the output buffers failed, such as when there is no active output buffer.
+    OutputBuffer,
+    /// A constant could not be registered, as a constant with the same name already exists.
+    ConstantExists(String),
+    /// A class constant was given where only a global constant can be defined.
+    ///
+    /// The enum carries the name of the constant.
+    ClassConstant(String),
+    /// An exception was thrown by a function called from Rust.
+    Exception,
+    /// The headers of the response could not be modified, as they have already been sent.
+    HeadersSent,
+    /// A header could not be added to the response, as it was invalid.
+    InvalidHeader(String),
+    /// A header or row of the information displayed by `phpinfo()` contains too many columns.
+    ///
+    /// The enum carries the number of columns.
+    TooManyColumns(usize),
+    /// A stream could not be opened.
+    ///
+    /// The enum carries the path of the stream.
+    StreamOpen(String),
+    /// A stream filter could not be registered, usually as the name is already in use.
+    StreamFilter(String),
+    /// A stream wrapper could not be registered, usually as the protocol is already in use.
+    StreamWrapper(String),
 }
 
 impl Display for Error {
@@ -83,6 +132,38 @@ impl Display for Error {
             Error::IntegerOverflow => {
                 write!(f, "Converting integer arguments resulted in an overflow.")
             }
+            Error::InvalidArgument(position, ty) => write!(
+                f,
+                "Invalid value of type {} given for argument #{}.",
+                ty, position
+            ),
+            Error::UnknownNamedParameter(name) => write!(f, "Unknown named parameter ${}", name),
+            Error::DuplicateNamedParameter(name) => {
+                write!(f, "Named parameter ${} overwrites previous argument", name)
+            }
+            Error::UnsupportedType(ty) => {
+                write!(f, "Type `{}` is not supported by this version of PHP.", ty)
+            }
+            Error::Instantiation(class) => write!(f, "Cannot instantiate class {}.", class),
+            Error::OutputBuffer => write!(f, "Failed to perform output buffer operation."),
+            Error::ConstantExists(name) => write!(f, "Constant `{}` is already defined.", name),
+            Error::ClassConstant(name) => {
+                write!(f, "Class constant `{}` cannot be defined.", name)
+            }
+            Error::Exception => write!(f, "An exception was thrown by the called function."),
+            Error::HeadersSent => write!(f, "Headers have already been sent."),
+            Error::InvalidHeader(header) => write!(f, "Invalid header `{}`.", header),
+            Error::TooManyColumns(n) => write!(
+                f,
+                "Expected at most {} columns, got {} columns.",
+                crate::php::info::MAX_COLUMNS,
+                n
+            ),
+            Error::StreamOpen(path) => write!(f, "Failed to open stream `{}`.", path),
+            Error::StreamFilter(name) => write!(f, "Failed to register stream filter `{}`.", name),
+            Error::StreamWrapper(protocol) => {
+                write!(f, "Failed to register stream wrapper `{}`.", protocol)
+            }
         }
     }
 }
@@ -97,6 +178,12 @@ impl From<NulError> for Error {
 
 impl From<Error> for PhpException {
     fn from(err: Error) -> Self {
-        Self::default(err.to_string())
+        match err {
+            Error::IncorrectArguments(..) => {
+                Self::new(err.to_string(), 0, ClassEntry::argument_count_error())
+            }
+            Error::InvalidArgument(..) => Self::new(err.to_string(), 0, ClassEntry::type_error()),
+            _ => Self::default(err.to_string()),
+        }
     }
 }
@@ -19,6 +19,13 @@ pub mod php;
 /// These declarations must happen before you declare your [`macro@php_startup`] function (or
 /// [`macro@php_module`] function if you do not have a startup function).
 ///
+/// The constant can be any type which implements [`IntoConst`], including integers, floats,
+/// booleans, strings, slices (exported as arrays) and [`Option`] (where [`None`] is exported as
+/// `null`). Constants marked with `#[deprecated]` are registered as deprecated, causing PHP to
+/// emit a deprecation notice when they are used.
+///
+/// [`IntoConst`]: crate::php::constants::IntoConst
+///
 /// # Example
 ///
 /// ```
@@ -28,6 +35,13 @@ pub mod php;
 ///
 /// #[php_const]
 /// const ANOTHER_CONST: &str = "Hello, world!";
+///
+/// #[php_const]
+/// const PRIMES: &[i64] = &[2, 3, 5, 7];
+///
+/// #[php_const]
+/// #[deprecated]
+/// const OLD_CONSTANT: bool = true;
 /// # #[php_module]
 /// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
 /// #     module
@@ -90,17 +104,23 @@ pub use ext_php_rs_derive::php_extern;
 /// but are not limited to the following:
 ///
 /// - Most primitive integers ([`i8`], [`i16`], [`i32`], [`i64`], [`u8`], [`u16`], [`u32`], [`u64`],
-/// [`usize`], [`isize`])
+///   [`usize`], [`isize`])
 /// - Double-precision floating point numbers ([`f64`])
 /// - [`bool`]
 /// - [`String`]
 /// - [`Vec<T>`] and [`HashMap<String, T>`](std::collections::HashMap) where `T: FromZval`.
 /// - [`Binary<T>`] for passing binary data as a string, where `T: Pack`.
 /// - [`Callable`] for receiving PHP callables, not applicable for return values.
+/// - [`Zval`] and [`ZvalRef`] for receiving values of any type. A [`Zval`] parameter is an owned
+///   copy of the value which can be held beyond the call, while [`ZvalRef`] borrows the value.
 /// - [`Option<T>`] where `T: FromZval`. When used as a parameter, the parameter will be
-/// deemed nullable, and will contain [`None`] when `null` is passed. When used as a return type,
-/// if [`None`] is returned the [`Zval`] will be set to null. Optional parameters *must* be of the
-/// type [`Option<T>`].
+///   deemed nullable, and will contain [`None`] when `null` is passed. When used as a return type,
+///   if [`None`] is returned the [`Zval`] will be set to null. Optional parameters *must* be of the
+///   type [`Option<T>`].
+///
+/// Integers, floats, booleans and strings are coerced from other types following the rules PHP
+/// applies to its own functions, unless the caller declared `strict_types`. See
+/// [`FromZvalCoerced`] for the rules.
 ///
 /// Additionally, you are able to return a variant of [`Result<T, E>`]. `T` must implement
 /// [`IntoZval`] and `E` must implement `Into<PhpException>`. If an error variant is returned, a
@@ -138,9 +158,7 @@ pub use ext_php_rs_derive::php_extern;
 ///     let result = hello(match name.val() {
 ///         Some(val) => val,
 ///         None => {
-///             PhpException::default("Invalid value given for argument `name`.".into())
-///                 .throw()
-///                 .expect("Failed to throw exception: Invalid value given for argument `name`.");
+///             name.throw_type_error();
 ///             return;
 ///         }
 ///     });
@@ -216,11 +234,30 @@ pub use ext_php_rs_derive::php_extern;
 /// # }
 /// ```
 ///
+/// The last parameter may be marked with the `#[variadic]` attribute to collect the remaining
+/// arguments given to the function, like `...$numbers` in PHP. The parameter must be a [`Vec<T>`],
+/// where each argument is converted into `T`, or a `&[&Zval]` to receive the arguments as they
+/// were given:
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// #[php_function]
+/// pub fn sum(#[variadic] numbers: Vec<i64>) -> i64 {
+///     numbers.iter().sum()
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
 /// [`Result<T, E>`]: std::result::Result
 /// [`FunctionBuilder`]: crate::php::function::FunctionBuilder
 /// [`FromZval`]: crate::php::types::zval::FromZval
+/// [`FromZvalCoerced`]: crate::php::types::coerce::FromZvalCoerced
 /// [`IntoZval`]: crate::php::types::zval::IntoZval
 /// [`Zval`]: crate::php::types::zval::Zval
+/// [`ZvalRef`]: crate::php::types::zval::ZvalRef
 /// [`Binary<T>`]: crate::php::types::binary::Binary
 /// [`Callable`]: crate::php::types::callable::Callable
 /// [`PhpException`]: crate::php::exceptions::PhpException
@@ -256,10 +293,16 @@ pub use ext_php_rs_derive::php_function;
 /// Methods can take a immutable or a mutable reference to `self`, but cannot consume `self`. They
 /// can also take no reference to `self` which indicates a static method.
 ///
+/// Methods can also take a parameter of type [`StaticContext`], which is not passed from PHP, but
+/// gives access to the class the method was called on. As static methods are inherited by
+/// subclasses, this can be used to return instances of the called class, the same as `new static`
+/// in PHP.
+///
 /// # Example
 ///
 /// ```no_run
 /// # use ext_php_rs::prelude::*;
+/// # use ext_php_rs::php::{class::StaticContext, types::object::ClassObject};
 /// #[php_class]
 /// #[derive(Debug, Default)]
 /// pub struct Human {
@@ -291,6 +334,11 @@ pub use ext_php_rs_derive::php_function;
 ///     pub fn get_age_limit() -> i32 {
 ///         Self::AGE_LIMIT
 ///     }
+///
+///     // Static method returning an instance of the called class - `Human::anonymous()`
+///     pub fn anonymous(ctx: StaticContext) -> PhpResult<ClassObject<'static, Human>> {
+///         Ok(ctx.new_instance(Human::default())?)
+///     }
 /// }
 ///
 /// #[php_module]
@@ -298,6 +346,8 @@ pub use ext_php_rs_derive::php_function;
 ///     module
 /// }
 /// ```
+///
+/// [`StaticContext`]: crate::php::class::StaticContext
 pub use ext_php_rs_derive::php_impl;
 
 /// Annotates a function that will be used by PHP to retrieve information about the module.
@@ -318,6 +368,10 @@ pub use ext_php_rs_derive::php_impl;
 /// If you have defined classes using the [`macro@php_class`] macro and you have not defined
 /// a startup function, it will be automatically declared and registered.
 ///
+/// Dependencies on other extensions can be declared with the `requires`, `optional` and
+/// `conflicts` arguments, which can each be given multiple times. PHP starts required and optional
+/// dependencies before the extension.
+///
 /// # Example
 ///
 /// The `get_module` function is required in every PHP extension. This is a bare minimum example,
@@ -336,8 +390,50 @@ pub use ext_php_rs_derive::php_impl;
 ///     module
 /// }
 /// ```
+///
+/// Declaring that the extension requires the `json` extension, and should be started after the
+/// `session` extension if it is loaded:
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// #[php_module(requires = "json", optional = "session")]
+/// pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+/// }
+/// ```
 pub use ext_php_rs_derive::php_module;
 
+/// Derives [`FromZval`] for an enum whose variants each contain a single value, converting a zval
+/// into the first variant whose value can be converted from it. The type of parameters accepting
+/// the enum is declared as the union of the types of the variants.
+///
+/// # Example
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// #[derive(FromZval)]
+/// pub enum IntOrString<'a> {
+///     Int(i64),
+///     Str(&'a str),
+/// }
+///
+/// // The parameter is declared as `int|string`.
+/// #[php_function]
+/// pub fn describe(value: IntOrString) -> String {
+///     match value {
+///         IntOrString::Int(i) => format!("int {}", i),
+///         IntOrString::Str(s) => format!("string {}", s),
+///     }
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
+/// [`FromZval`]: crate::php::types::zval::FromZval
+pub use ext_php_rs_derive::FromZval;
+
 /// Annotates a struct that will be exported to PHP as a class.
 ///
 /// The struct that this attribute is used on must implement [`Default`], as this is used to
@@ -357,6 +453,19 @@ pub use ext_php_rs_derive::php_module;
 ///   may be any valid expression.
 /// * `#[implements(ce)]` - Implements an interface on the new class. Can be used multiple times,
 ///   and `ce` may be any valid expression.
+/// * `#[debug_info]` - Shows the [`Debug`] representation of the struct when the object is
+///   inspected with `var_dump`, `print_r` or a debugger. Use `#[debug_info(custom)]` to show the
+///   entries returned by the [`PhpDebug`] implementation of the struct instead.
+///
+/// To extend another class exported from Rust, mark the first field of the struct, holding the
+/// parent struct, with the `#[parent]` attribute. The parent class is then set as the parent of the
+/// new class, and the methods and properties of the parent class can be used on instances of the new
+/// class. The parent is accessible from Rust through the field. `#[extends]` cannot be used
+/// together with `#[parent]`, and must not be given the class entry of a class exported from Rust.
+///
+/// Classes exported from Rust can also be extended by classes defined in PHP. The Rust struct is
+/// initialized for the objects of these classes, and the Rust constructor can be called through
+/// `parent::__construct()`.
 ///
 /// This attribute (and its associated structs) must be defined *above* the startup function (which
 /// is annotated by the [`macro@php_startup`] macro, or automatically generated just above the
@@ -407,6 +516,40 @@ pub use ext_php_rs_derive::php_module;
 ///     module
 /// }
 /// ```
+///
+/// Extend the `Example` class, storing the `Example` struct in the first field:
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// #[php_class]
+/// #[derive(Default)]
+/// pub struct Example {
+///     #[prop]
+///     x: i32,
+/// }
+///
+/// #[php_class]
+/// #[derive(Default)]
+/// pub struct ExtendedExample {
+///     #[parent]
+///     base: Example,
+///     y: i32,
+/// }
+///
+/// #[php_impl]
+/// impl ExtendedExample {
+///     pub fn sum(&self) -> i32 {
+///         self.base.x + self.y
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+/// }
+/// ```
+///
+/// [`PhpDebug`]: crate::php::types::object::PhpDebug
 pub use ext_php_rs_derive::php_class;
 
 /// Annotates a function that will be called by PHP when the module starts up. Generally used to
@@ -446,10 +589,13 @@ pub mod prelude {
     #[cfg_attr(docs, doc(cfg(feature = "closure")))]
     pub use crate::php::types::closure::Closure;
     pub use crate::php_class;
+    pub use crate::FromZval;
     pub use crate::php_const;
     pub use crate::php_extern;
     pub use crate::php_function;
     pub use crate::php_impl;
     pub use crate::php_module;
+    pub use crate::php_print;
+    pub use crate::php_println;
     pub use crate::php_startup;
 }
@@ -163,3 +163,44 @@ macro_rules! throw {
         return;
     };
 }
+
+/// Prints to the PHP output stream, the same as `echo` in PHP. Takes the same arguments as the
+/// [`print!`] macro.
+///
+/// The output passes through the active output buffers. See the
+/// [`output`](crate::php::output) module for more control over the output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php_print;
+///
+/// php_print!("Hello, {}!", "world");
+/// ```
+#[macro_export]
+macro_rules! php_print {
+    ($($arg: tt)*) => {
+        $crate::php::output::_print(::std::format_args!($($arg)*))
+    };
+}
+
+/// Prints to the PHP output stream with a newline, the same as `echo` in PHP. Takes the same
+/// arguments as the [`println!`] macro.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php_println;
+///
+/// php_println!("Hello, {}!", "world");
+/// ```
+#[macro_export]
+macro_rules! php_println {
+    () => {
+        $crate::php_print!("\n")
+    };
+
+    ($($arg: tt)*) => {
+        $crate::php::output::_print(::std::format_args!("{}\n", ::std::format_args!($($arg)*)))
+    };
+}
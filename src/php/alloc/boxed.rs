@@ -0,0 +1,257 @@
+//! Smart pointers owning a value allocated through the Zend memory manager.
+
+use std::{
+    alloc::Layout,
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use super::{efree, emalloc, pefree, pemalloc, RequestBound, ZEND_MM_ALIGNMENT};
+
+/// The alignment guaranteed by `malloc()`, which is used to allocate persistent memory.
+const MALLOC_ALIGNMENT: usize = 2 * mem::size_of::<usize>();
+
+/// Allocates memory for a value of type `T` with the given allocation function, returning a
+/// dangling pointer for zero-sized types.
+///
+/// # Panics
+///
+/// Panics if the alignment of `T` is greater than the alignment guaranteed by the allocator.
+fn allocate<T>(alloc: fn(Layout) -> *mut u8, max_align: usize) -> NonNull<T> {
+    let layout = Layout::new::<T>();
+    assert!(
+        layout.align() <= max_align,
+        "Types aligned to more than {} bytes cannot be allocated through the Zend memory manager.",
+        max_align
+    );
+
+    if layout.size() == 0 {
+        return NonNull::dangling();
+    }
+
+    NonNull::new(alloc(layout) as *mut T).unwrap_or_else(|| std::alloc::handle_alloc_error(layout))
+}
+
+/// A pointer to a value allocated through the Zend memory manager with `emalloc()`, which is
+/// freed with `efree()` when dropped.
+///
+/// The value is bound to the request it was allocated in, as PHP releases all request-bound
+/// memory when the request ends. In debug builds, creating a box outside of a request, or using
+/// it after the request ended, panics.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::alloc::boxed::ZBox;
+///
+/// let mut counter = ZBox::new(0u64);
+/// *counter += 1;
+/// assert_eq!(*counter, 1);
+/// ```
+pub struct ZBox<T> {
+    ptr: NonNull<T>,
+    request: RequestBound,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ZBox<T> {
+    /// Allocates memory for a value through the Zend memory manager and moves the value into
+    /// it.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value to move into the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is greater than the alignment guaranteed by the Zend
+    /// memory manager, which is 8 bytes.
+    pub fn new(value: T) -> Self {
+        let request = RequestBound::new();
+        let ptr = allocate::<T>(emalloc, ZEND_MM_ALIGNMENT);
+        unsafe { ptr::write(ptr.as_ptr(), value) };
+
+        Self {
+            ptr,
+            request,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consumes the box, returning a pointer to the value. The memory must later be freed with
+    /// [`ZBox::from_raw`], or by passing the value to PHP, which frees it with `efree()`.
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Creates a box from a pointer to a value allocated with `emalloc()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `ptr` - The pointer to the value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that the pointer is non-null, points to a valid value of type
+    /// `T`, and was allocated with `emalloc()` during the current request, such as by
+    /// [`ZBox::into_raw`]. Ownership of the value is transferred to the box.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            request: RequestBound::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for ZBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.request.check();
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for ZBox<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.request.check();
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Drop for ZBox<T> {
+    fn drop(&mut self) {
+        self.request.check();
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            if mem::size_of::<T>() != 0 {
+                efree(self.ptr.as_ptr() as *mut u8);
+            }
+        }
+    }
+}
+
+impl<T: Debug> Debug for ZBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display> Display for ZBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A pointer to a value allocated with `pemalloc()`, which is freed with `pefree()` when
+/// dropped.
+///
+/// Unlike [`ZBox`], the value persists across requests, so it can be created while the
+/// extension is starting up and held in a static.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::alloc::boxed::PBox;
+///
+/// let name = PBox::new(String::from("config"));
+/// assert_eq!(name.as_str(), "config");
+/// ```
+pub struct PBox<T> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> PBox<T> {
+    /// Allocates persistent memory for a value and moves the value into it.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value to move into the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is greater than the alignment guaranteed by `malloc()`.
+    pub fn new(value: T) -> Self {
+        let ptr = allocate::<T>(pemalloc, MALLOC_ALIGNMENT);
+        unsafe { ptr::write(ptr.as_ptr(), value) };
+
+        Self {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consumes the box, returning a pointer to the value. The memory must later be freed with
+    /// [`PBox::from_raw`], or by passing the value to PHP, which frees it with `pefree()`.
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Creates a box from a pointer to a value allocated with `pemalloc()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `ptr` - The pointer to the value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that the pointer is non-null, points to a valid value of type
+    /// `T`, and was allocated with `pemalloc()`, such as by [`PBox::into_raw`]. Ownership of the
+    /// value is transferred to the box.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            _marker: PhantomData,
+        }
+    }
+}
+
+// SAFETY: The box owns the value, and persistent memory can be freed from any thread.
+unsafe impl<T: Send> Send for PBox<T> {}
+unsafe impl<T: Sync> Sync for PBox<T> {}
+
+impl<T> Deref for PBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for PBox<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Drop for PBox<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            if mem::size_of::<T>() != 0 {
+                pefree(self.ptr.as_ptr() as *mut u8);
+            }
+        }
+    }
+}
+
+impl<T: Debug> Debug for PBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display> Display for PBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
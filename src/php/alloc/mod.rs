@@ -0,0 +1,416 @@
+//! Functions relating to the Zend Memory Manager, used to allocate request-bound memory.
+//!
+//! Along with the raw allocation functions, typed smart pointers and collections are provided
+//! which allocate through the memory manager: [`ZBox`](boxed::ZBox), [`ZVec`](vec::ZVec) and
+//! [`ZString`](string::ZString) for request-bound memory, and [`PBox`](boxed::PBox) for memory
+//! which persists across requests.
+
+pub mod boxed;
+pub mod string;
+pub mod vec;
+
+use crate::bindings::{_efree, _emalloc, _erealloc, ext_php_rs_pefree, ext_php_rs_pemalloc};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    ffi::c_void,
+    mem,
+    num::NonZeroU64,
+    ptr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::globals::ExecutorGlobals;
+
+/// The file name passed to the Zend memory manager in debug builds, reported alongside leaked
+/// memory.
+#[cfg(php_debug)]
+const FILENAME: *const std::os::raw::c_char =
+    concat!(file!(), "\0").as_ptr() as *const std::os::raw::c_char;
+
+/// Uses the PHP memory allocator to allocate request-bound memory.
+///
+/// # Parameters
+///
+/// * `layout` - The layout of the requested memory.
+///
+/// # Returns
+///
+/// A pointer to the memory allocated.
+pub fn emalloc(layout: Layout) -> *mut u8 {
+    // TODO account for alignment
+    let size = layout.size();
+
+    (unsafe {
+        #[cfg(php_debug)]
+        {
+            _emalloc(size as _, FILENAME, line!(), std::ptr::null_mut(), 0)
+        }
+        #[cfg(not(php_debug))]
+        {
+            _emalloc(size as _)
+        }
+    }) as *mut u8
+}
+
+/// Resizes memory which was allocated through the PHP memory manager, returning a pointer to the
+/// resized memory. The contents of the memory are preserved up to the smaller of the old and new
+/// sizes.
+///
+/// # Parameters
+///
+/// * `ptr` - The pointer to the memory to resize.
+/// * `size` - The new size of the memory.
+///
+/// # Safety
+///
+/// Caller must guarantee that the given pointer is valid (aligned and non-null) and
+/// was originally allocated through the Zend memory manager.
+pub unsafe fn erealloc(ptr: *mut u8, size: usize) -> *mut u8 {
+    #[cfg(php_debug)]
+    {
+        _erealloc(
+            ptr as *mut c_void,
+            size as _,
+            FILENAME,
+            line!(),
+            std::ptr::null_mut(),
+            0,
+        ) as *mut u8
+    }
+    #[cfg(not(php_debug))]
+    {
+        _erealloc(ptr as *mut c_void, size as _) as *mut u8
+    }
+}
+
+/// Frees a given memory pointer which was allocated through the PHP memory manager.
+///
+/// # Parameters
+///
+/// * `ptr` - The pointer to the memory to free.
+///
+/// # Safety
+///
+/// Caller must guarantee that the given pointer is valid (aligned and non-null) and
+/// was originally allocated through the Zend memory manager.
+pub unsafe fn efree(ptr: *mut u8) {
+    #[cfg(php_debug)]
+    {
+        _efree(
+            ptr as *mut c_void,
+            FILENAME,
+            line!(),
+            std::ptr::null_mut(),
+            0,
+        )
+    }
+    #[cfg(not(php_debug))]
+    {
+        _efree(ptr as *mut c_void)
+    }
+}
+
+/// Uses the PHP memory allocator to allocate persistent memory, which is not freed when the
+/// request ends. The process is aborted if there is no memory available.
+///
+/// # Parameters
+///
+/// * `layout` - The layout of the requested memory.
+///
+/// # Returns
+///
+/// A pointer to the memory allocated.
+pub fn pemalloc(layout: Layout) -> *mut u8 {
+    unsafe { ext_php_rs_pemalloc(layout.size() as _) as *mut u8 }
+}
+
+/// Frees a given memory pointer which was allocated through [`pemalloc`].
+///
+/// # Parameters
+///
+/// * `ptr` - The pointer to the memory to free.
+///
+/// # Safety
+///
+/// Caller must guarantee that the given pointer is valid (aligned and non-null) and
+/// was originally allocated through [`pemalloc`].
+pub unsafe fn pefree(ptr: *mut u8) {
+    ext_php_rs_pefree(ptr as *mut c_void)
+}
+
+/// A global allocator which allocates memory through the Zend memory manager while a request is
+/// being handled, so that Rust allocations count towards `memory_limit` and leaked memory is
+/// reported by debug builds of PHP.
+///
+/// Outside of a request, such as while the extension is starting up or shutting down, and on
+/// threads which were not created by PHP, memory is allocated through the system allocator.
+/// Each allocation records which allocator and request it came from: memory from the system
+/// allocator is always resized and freed through the system allocator, and memory from an
+/// earlier request, which PHP has already released, is leaked rather than freed again.
+///
+/// Memory allocated during a request is released by PHP when the request ends, so it must not
+/// outlive the request, the same as memory allocated by PHP itself. Values which live across
+/// requests, such as those held in statics, must be created outside of a request. In particular,
+/// lazily initialized statics, such as those created with `lazy_static!` or `OnceCell`, are
+/// unsafe to use with this allocator unless they are initialized before the first request, as
+/// their memory would be released at the end of the request which initialized them.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::alloc::ZendAllocator;
+///
+/// #[global_allocator]
+/// static GLOBAL: ZendAllocator = ZendAllocator::new();
+/// ```
+#[derive(Debug, Default)]
+pub struct ZendAllocator {
+    _private: (),
+}
+
+/// Stored before each allocation made by [`ZendAllocator`].
+#[repr(C)]
+struct Header {
+    /// The pointer returned by the underlying allocator.
+    raw: *mut u8,
+    /// The request the memory was allocated in through the Zend memory manager, or [`None`] if
+    /// the memory was allocated through the system allocator.
+    request: Option<NonZeroU64>,
+}
+
+/// The alignment guaranteed by the Zend memory manager, `ZEND_MM_ALIGNMENT`.
+const ZEND_MM_ALIGNMENT: usize = crate::bindings::ZEND_MM_ALIGNMENT as usize;
+
+thread_local! {
+    /// Whether the current thread is the thread PHP runs on, when PHP is not thread safe.
+    static PHP_THREAD: Cell<bool> = const { Cell::new(false) };
+
+    /// The request being handled on the current thread.
+    static REQUEST: Cell<Option<NonZeroU64>> = const { Cell::new(None) };
+}
+
+/// The number of requests which have started on any thread, used to identify requests.
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// Records that a request has started on the current thread. Called when requests start, so that
+/// memory is only freed through the Zend memory manager during the request it was allocated in.
+pub(crate) fn request_started() {
+    let request = NonZeroU64::new(REQUESTS.fetch_add(1, Ordering::Relaxed) + 1);
+    REQUEST.with(|current| current.set(request));
+}
+
+/// Records that the request being handled on the current thread has ended. Called after the
+/// request has been deactivated, before PHP releases its memory.
+pub(crate) fn request_ended() {
+    REQUEST.with(|current| current.set(None));
+}
+
+/// Identifies the request being handled on the current thread, or [`None`] if no request is
+/// being handled.
+fn current_request() -> Option<NonZeroU64> {
+    REQUEST.try_with(Cell::get).unwrap_or(None)
+}
+
+/// Records the request memory was allocated in, checking in debug builds that the memory is
+/// only used during that request.
+#[derive(Debug, Clone, Copy)]
+struct RequestBound {
+    #[cfg(debug_assertions)]
+    request: Option<NonZeroU64>,
+}
+
+impl RequestBound {
+    /// Records the current request, asserting in debug builds that a request is being handled.
+    fn new() -> Self {
+        #[cfg(debug_assertions)]
+        {
+            let request = current_request();
+            debug_assert!(
+                request.is_some(),
+                "Request-bound memory cannot be allocated outside of a request."
+            );
+            Self { request }
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            Self {}
+        }
+    }
+
+    /// Asserts in debug builds that the request the memory was allocated in is still being
+    /// handled.
+    #[inline]
+    fn check(&self) {
+        #[cfg(debug_assertions)]
+        debug_assert!(
+            current_request() == self.request,
+            "Request-bound memory was used after the request it was allocated in ended."
+        );
+    }
+}
+
+/// Marks the current thread as the thread PHP runs on. Called when the module is built, which
+/// happens on the thread which starts PHP.
+pub(crate) fn mark_php_thread() {
+    PHP_THREAD.with(|php_thread| php_thread.set(true));
+}
+
+impl ZendAllocator {
+    /// Creates a new Zend allocator.
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Returns whether memory can currently be allocated through the Zend memory manager, which
+    /// is the case while a request is being handled on a thread owned by PHP.
+    fn use_zend_mm() -> bool {
+        #[cfg(php_zts)]
+        let php_thread = !unsafe { crate::bindings::tsrm_get_ls_cache() }.is_null();
+        #[cfg(not(php_zts))]
+        let php_thread = PHP_THREAD
+            .try_with(|php_thread| php_thread.get())
+            .unwrap_or(false);
+
+        php_thread && ExecutorGlobals::get().active
+    }
+
+    /// Returns the request to allocate memory in through the Zend memory manager, or [`None`] if
+    /// memory must be allocated through the system allocator.
+    fn zend_request() -> Option<NonZeroU64> {
+        if Self::use_zend_mm() {
+            current_request()
+        } else {
+            None
+        }
+    }
+
+    /// Allocates memory in the given request, or through the system allocator if there is no
+    /// request.
+    unsafe fn allocate(layout: Layout, request: Option<NonZeroU64>) -> *mut u8 {
+        let (outer, offset) = match Self::outer_layout(layout) {
+            Some(outer) => outer,
+            None => return ptr::null_mut(),
+        };
+
+        let raw = if request.is_some() {
+            emalloc(outer)
+        } else {
+            System.alloc(outer)
+        };
+
+        Self::finish(raw, layout, offset, request)
+    }
+
+    /// Returns the layout of the memory requested from the underlying allocator, and the offset
+    /// of the allocation from the start of the memory.
+    fn outer_layout(layout: Layout) -> Option<(Layout, usize)> {
+        let align = layout.align().max(mem::align_of::<Header>());
+        let offset = mem::size_of::<Header>().max(align);
+
+        // The underlying allocators only guarantee the alignment of the header, so padding is
+        // added to align the allocation.
+        let padding = align - mem::align_of::<Header>().min(align);
+        let size = layout.size().checked_add(offset)?.checked_add(padding)?;
+
+        Some((
+            Layout::from_size_align(size, mem::align_of::<Header>()).ok()?,
+            offset,
+        ))
+    }
+
+    /// Aligns the allocation within the memory returned by the underlying allocator, and writes
+    /// the header before it.
+    unsafe fn finish(
+        raw: *mut u8,
+        layout: Layout,
+        offset: usize,
+        request: Option<NonZeroU64>,
+    ) -> *mut u8 {
+        if raw.is_null() {
+            return ptr::null_mut();
+        }
+
+        let start = raw.add(offset);
+        let ptr = start.add(start.align_offset(layout.align()));
+        ptr::write(Self::header(ptr), Header { raw, request });
+        ptr
+    }
+
+    /// Returns a pointer to the header of an allocation.
+    unsafe fn header(ptr: *mut u8) -> *mut Header {
+        (ptr as *mut Header).sub(1)
+    }
+}
+
+unsafe impl GlobalAlloc for ZendAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::allocate(layout, Self::zend_request())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let header = ptr::read(Self::header(ptr));
+        match header.request {
+            None => {
+                if let Some((outer, _)) = Self::outer_layout(layout) {
+                    System.dealloc(header.raw, outer);
+                }
+            }
+            Some(request) if Some(request) == current_request() => efree(header.raw),
+            // Memory from an earlier request was already released by PHP when the request ended.
+            Some(_) => {}
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let header = ptr::read(Self::header(ptr));
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+
+        // Memory from the system allocator stays with the system allocator, while memory from the
+        // Zend memory manager moves to the current request, if any.
+        let request = header.request.and_then(|_| Self::zend_request());
+
+        // Memory from the Zend memory manager is resized in place when the allocation is at a
+        // fixed offset, which is the case when the alignment is guaranteed by the manager.
+        if request.is_some() && header.request == request && layout.align() <= ZEND_MM_ALIGNMENT {
+            let (outer, offset) = match Self::outer_layout(new_layout) {
+                Some(outer) => outer,
+                None => return ptr::null_mut(),
+            };
+
+            if ptr == header.raw.add(offset) {
+                let raw = erealloc(header.raw, outer.size());
+                return Self::finish(raw, new_layout, offset, request);
+            }
+        }
+
+        let new_ptr = Self::allocate(new_layout, request);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::Layout;
+
+    use super::ZendAllocator;
+
+    #[test]
+    fn test_outer_layout() {
+        let (outer, offset) = ZendAllocator::outer_layout(Layout::new::<u64>()).unwrap();
+        assert_eq!(offset, 16);
+        assert_eq!(outer.size(), 24);
+
+        let layout = Layout::from_size_align(100, 64).unwrap();
+        let (outer, offset) = ZendAllocator::outer_layout(layout).unwrap();
+        assert_eq!(offset, 64);
+        assert!(outer.size() >= offset + 100 + 64 - outer.align());
+
+        let layout = Layout::from_size_align(isize::MAX as usize - 8, 8).unwrap();
+        assert!(ZendAllocator::outer_layout(layout).is_none());
+    }
+}
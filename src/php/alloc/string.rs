@@ -0,0 +1,130 @@
+//! A growable UTF-8 string allocated through the Zend memory manager.
+
+use std::{
+    fmt::{self, Debug, Display, Write},
+    ops::Deref,
+    str,
+};
+
+use super::vec::ZVec;
+
+/// A growable UTF-8 string, the equivalent of [`String`], whose contents are allocated through
+/// the Zend memory manager with `emalloc()`. Allocations count towards `memory_limit`.
+///
+/// The string is bound to the request it was created in, as PHP releases all request-bound
+/// memory when the request ends. In debug builds, creating a string outside of a request, or
+/// using it after the request ended, panics.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fmt::Write;
+/// use ext_php_rs::php::alloc::string::ZString;
+///
+/// let mut greeting = ZString::from("Hello");
+/// write!(greeting, ", {}!", "world").unwrap();
+/// assert_eq!(greeting.as_str(), "Hello, world!");
+/// ```
+#[derive(Default)]
+pub struct ZString {
+    bytes: ZVec<u8>,
+}
+
+impl ZString {
+    /// Creates a new, empty string. No memory is allocated until text is pushed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty string with space for at least the given number of bytes.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - The number of bytes to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bytes: ZVec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of bytes the string can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Appends a string slice to the end of the string.
+    ///
+    /// # Parameters
+    ///
+    /// * `s` - The string slice to append.
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend(s.bytes());
+    }
+
+    /// Appends a character to the end of the string.
+    ///
+    /// # Parameters
+    ///
+    /// * `c` - The character to append.
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Removes all text from the string, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Returns the contents of the string as a string slice.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The bytes are only ever appended from string slices.
+        unsafe { str::from_utf8_unchecked(&self.bytes) }
+    }
+}
+
+impl Deref for ZString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<&str> for ZString {
+    fn from(s: &str) -> Self {
+        let mut string = Self::with_capacity(s.len());
+        string.push_str(s);
+        string
+    }
+}
+
+impl Write for ZString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl PartialEq<str> for ZString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ZString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for ZString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Debug for ZString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
@@ -0,0 +1,231 @@
+//! A growable array allocated through the Zend memory manager.
+
+use std::{
+    alloc::Layout,
+    fmt::{self, Debug},
+    iter::FromIterator,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    slice,
+};
+
+use super::{efree, emalloc, erealloc, RequestBound, ZEND_MM_ALIGNMENT};
+
+/// A contiguous growable array, the equivalent of [`Vec`], whose elements are allocated through
+/// the Zend memory manager with `emalloc()`. Allocations count towards `memory_limit`.
+///
+/// The vector is bound to the request it was created in, as PHP releases all request-bound
+/// memory when the request ends. In debug builds, creating a vector outside of a request, or
+/// using it after the request ended, panics.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::alloc::vec::ZVec;
+///
+/// let mut ids = ZVec::new();
+/// ids.push(1);
+/// ids.extend(vec![2, 3]);
+/// assert_eq!(&ids[..], &[1, 2, 3]);
+/// ```
+pub struct ZVec<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    cap: usize,
+    request: RequestBound,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ZVec<T> {
+    /// Creates a new, empty vector. No memory is allocated until elements are pushed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is greater than the alignment guaranteed by the Zend
+    /// memory manager, which is 8 bytes.
+    pub fn new() -> Self {
+        assert!(
+            mem::align_of::<T>() <= ZEND_MM_ALIGNMENT,
+            "Types aligned to more than {} bytes cannot be allocated through the Zend memory manager.",
+            ZEND_MM_ALIGNMENT
+        );
+
+        Self {
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: if mem::size_of::<T>() == 0 {
+                usize::MAX
+            } else {
+                0
+            },
+            request: RequestBound::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty vector with space for at least the given number of elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - The number of elements to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Reserves space for at least the given number of additional elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `additional` - The number of elements to reserve space for.
+    pub fn reserve(&mut self, additional: usize) {
+        self.request.check();
+
+        let required = self.len.checked_add(additional).expect("Capacity overflow");
+        if required <= self.cap {
+            return;
+        }
+
+        let cap = required.max(self.cap * 2).max(4);
+        let layout = Layout::array::<T>(cap).expect("Capacity overflow");
+        let ptr = if self.cap == 0 {
+            emalloc(layout)
+        } else {
+            unsafe { erealloc(self.ptr.as_ptr() as *mut u8, layout.size()) }
+        };
+
+        self.ptr =
+            NonNull::new(ptr as *mut T).unwrap_or_else(|| std::alloc::handle_alloc_error(layout));
+        self.cap = cap;
+    }
+
+    /// Appends an element to the end of the vector.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The element to append.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.cap {
+            self.reserve(1);
+        }
+
+        unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value) };
+        self.len += 1;
+    }
+
+    /// Removes the last element from the vector and returns it, or [`None`] if the vector is
+    /// empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.request.check();
+
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.len)) })
+    }
+
+    /// Shortens the vector to the given length, dropping the remaining elements. Does nothing if
+    /// the vector is already shorter.
+    ///
+    /// # Parameters
+    ///
+    /// * `len` - The length to shorten the vector to.
+    pub fn truncate(&mut self, len: usize) {
+        self.request.check();
+
+        if len >= self.len {
+            return;
+        }
+
+        let remaining =
+            ptr::slice_from_raw_parts_mut(unsafe { self.ptr.as_ptr().add(len) }, self.len - len);
+        self.len = len;
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+
+    /// Removes all elements from the vector, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<T> Default for ZVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for ZVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.request.check();
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for ZVec<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.request.check();
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for ZVec<T> {
+    fn drop(&mut self) {
+        self.clear();
+        if mem::size_of::<T>() != 0 && self.cap != 0 {
+            unsafe { efree(self.ptr.as_ptr() as *mut u8) };
+        }
+    }
+}
+
+impl<T> Extend<T> for ZVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ZVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T: Clone> From<&[T]> for ZVec<T> {
+    fn from(values: &[T]) -> Self {
+        values.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for ZVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
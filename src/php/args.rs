@@ -1,23 +1,38 @@
 //! Builder and objects relating to function and method arguments.
 
-use std::{ffi::CString, ptr};
+use std::{
+    collections::HashMap,
+    ffi::{CStr, CString},
+    ptr,
+};
 
 use super::{
+    class::ClassEntry,
     enums::DataType,
+    exceptions::{error, throw},
     execution_data::ExecutionData,
+    globals::ExecutorGlobals,
     types::{
+        array::HashTable,
+        coerce::FromZvalCoerced,
+        type_spec::TypeSpec,
         zval::{FromZval, IntoZvalDyn, Zval},
-        ZendType,
     },
 };
 
 use crate::{
     bindings::{
         _zend_expected_type, _zend_expected_type_Z_EXPECTED_ARRAY,
-        _zend_expected_type_Z_EXPECTED_BOOL, _zend_expected_type_Z_EXPECTED_DOUBLE,
-        _zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_OBJECT,
-        _zend_expected_type_Z_EXPECTED_RESOURCE, _zend_expected_type_Z_EXPECTED_STRING,
-        zend_internal_arg_info, zend_wrong_parameters_count_error,
+        _zend_expected_type_Z_EXPECTED_ARRAY_OR_LONG,
+        _zend_expected_type_Z_EXPECTED_ARRAY_OR_STRING, _zend_expected_type_Z_EXPECTED_BOOL,
+        _zend_expected_type_Z_EXPECTED_DOUBLE, _zend_expected_type_Z_EXPECTED_FUNC,
+        _zend_expected_type_Z_EXPECTED_ITERABLE, _zend_expected_type_Z_EXPECTED_LONG,
+        _zend_expected_type_Z_EXPECTED_NUMBER, _zend_expected_type_Z_EXPECTED_OBJECT,
+        _zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING, _zend_expected_type_Z_EXPECTED_RESOURCE,
+        _zend_expected_type_Z_EXPECTED_STRING, _zend_expected_type_Z_EXPECTED_STRING_OR_LONG,
+        get_active_class_name, get_active_function_name, zend_argument_error,
+        zend_argument_type_error, zend_internal_arg_info, zend_wrong_parameters_count_error,
+        zend_zval_type_name, E_DEPRECATED,
     },
     errors::{Error, Result},
 };
@@ -26,12 +41,16 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Arg<'a> {
     name: String,
-    _type: DataType,
+    ty: TypeSpec,
     as_ref: bool,
     allow_null: bool,
     variadic: bool,
     default_value: Option<String>,
     zval: Option<&'a Zval>,
+    variadic_zvals: Vec<&'a Zval>,
+    named_zvals: Vec<(String, &'a Zval)>,
+    position: usize,
+    strict: bool,
 }
 
 impl<'a> Arg<'a> {
@@ -40,16 +59,20 @@ impl<'a> Arg<'a> {
     /// # Parameters
     ///
     /// * `name` - The name of the parameter.
-    /// * `_type` - The type of the parameter.
-    pub fn new<T: Into<String>>(name: T, _type: DataType) -> Self {
+    /// * `ty` - The type of the parameter, either a [`DataType`] or a [`TypeSpec`].
+    pub fn new<T: Into<String>, S: Into<TypeSpec>>(name: T, ty: S) -> Self {
         Arg {
             name: name.into(),
-            _type,
+            ty: ty.into(),
             as_ref: false,
             allow_null: false,
             variadic: false,
             default_value: None,
             zval: None,
+            variadic_zvals: vec![],
+            named_zvals: vec![],
+            position: 0,
+            strict: false,
         }
     }
 
@@ -66,13 +89,20 @@ impl<'a> Arg<'a> {
         self
     }
 
+    /// Returns the declared type of the argument.
+    pub fn type_spec(&self) -> &TypeSpec {
+        &self.ty
+    }
+
     /// Sets the argument as nullable.
     pub fn allow_null(mut self) -> Self {
         self.allow_null = true;
         self
     }
 
-    /// Sets the default value for the argument.
+    /// Sets the default value for the argument. The value is a PHP expression, such as `'hello'`,
+    /// `5` or `null`, which is shown through reflection and used by PHP when the argument is
+    /// skipped by passing a later argument by name.
     pub fn default<T: Into<String>>(mut self, default: T) -> Self {
         self.default_value = Some(default.into());
         self
@@ -85,6 +115,91 @@ impl<'a> Arg<'a> {
         self.zval.and_then(|zv| T::from_zval(zv))
     }
 
+    /// Attempts to retrieve the value of the argument, coercing it into the type `T` following
+    /// PHP's rules for the arguments of internal functions. The value is only coerced when the
+    /// caller does not use strict types. See [`FromZvalCoerced`] for the rules which are applied.
+    /// This will be None until the ArgParser is used to parse the arguments.
+    ///
+    /// A deprecation notice is raised when `null` is given in place of a type which is not
+    /// nullable, and [`None`] is returned if the notice was turned into an exception.
+    pub fn coerced_val<T: FromZvalCoerced<'a>>(&self) -> Option<T> {
+        let zval = self.zval?;
+        let val = T::from_zval_coerced(zval, self.strict)?;
+
+        let ty = T::type_spec();
+        if zval.is_null() && !ty.is_nullable() {
+            self.null_deprecated(&ty);
+            if !ExecutorGlobals::get().exception.is_null() {
+                return None;
+            }
+        }
+
+        Some(val)
+    }
+
+    /// Raises the deprecation notice for `null` being given for a parameter which is not nullable.
+    fn null_deprecated(&self, ty: &TypeSpec) {
+        let mut space = ptr::null();
+
+        // SAFETY: Exported C functions are safe, and the returned strings are either static or
+        // owned by the active function.
+        let (class, func) = unsafe {
+            let class = get_active_class_name(&mut space);
+            (
+                format!(
+                    "{}{}",
+                    CStr::from_ptr(class).to_string_lossy(),
+                    CStr::from_ptr(space).to_string_lossy()
+                ),
+                CStr::from_ptr(get_active_function_name()).to_string_lossy(),
+            )
+        };
+
+        let _ = error(
+            E_DEPRECATED,
+            &format!(
+                "{}{}(): Passing null to parameter #{} (${}) of type {} is deprecated",
+                class,
+                func,
+                self.position + 1,
+                self.name,
+                ty
+            ),
+        );
+    }
+
+    /// Throws a `TypeError` stating that the value given for the argument is not of the type of
+    /// the argument, matching the errors PHP throws for its own functions, such as
+    /// `foo(): Argument #1 ($bar) must be of type int, string given`.
+    pub fn throw_type_error(&self) {
+        throw_type_error(self.position + 1, &self.expected_type(), self.zval);
+    }
+
+    /// Throws a `TypeError` stating that one of the values given for a variadic argument is not
+    /// of the type of the argument.
+    ///
+    /// # Parameters
+    ///
+    /// * `position` - The position of the value, counting from the first argument of the
+    ///   function starting from 1, as returned in [`Error::InvalidArgument`].
+    pub fn throw_variadic_type_error(&self, position: usize) {
+        let zval = position
+            .checked_sub(self.position + 1)
+            .and_then(|i| self.variadic_zvals.get(i))
+            .copied();
+
+        throw_type_error(position, &self.ty, zval);
+    }
+
+    /// Returns the type of the argument, including whether it is nullable.
+    fn expected_type(&self) -> TypeSpec {
+        if self.allow_null {
+            self.ty.clone().nullable()
+        } else {
+            self.ty.clone()
+        }
+    }
+
     /// Attempts to return a reference to the arguments internal Zval.
     ///
     /// # Returns
@@ -95,6 +210,59 @@ impl<'a> Arg<'a> {
         self.zval
     }
 
+    /// Returns the values given for a variadic argument. This will be empty until the
+    /// [`ArgParser`] is used to parse the arguments, or when no values were given for the
+    /// argument.
+    pub fn variadic_zvals(&self) -> &[&'a Zval] {
+        &self.variadic_zvals
+    }
+
+    /// Attempts to retrieve the values given for a variadic argument, converting each of them
+    /// into the type `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] containing the position of the first value which could
+    /// not be converted, counting from the first argument of the function.
+    pub fn variadic_vals<T: FromZval<'a>>(&self) -> Result<Vec<T>> {
+        self.variadic_zvals
+            .iter()
+            .enumerate()
+            .map(|(i, zv)| {
+                T::from_zval(zv)
+                    .ok_or_else(|| Error::InvalidArgument(self.position + i + 1, zv.get_type()))
+            })
+            .collect()
+    }
+
+    /// Returns the named arguments collected by a variadic argument, which are the named arguments
+    /// given to the function that do not match any of its parameters. The arguments are in the
+    /// order they were given.
+    pub fn variadic_named_zvals(&self) -> &[(String, &'a Zval)] {
+        &self.named_zvals
+    }
+
+    /// Returns all the values collected by a variadic argument, keyed the same way as PHP keys
+    /// the array of a variadic parameter. Positional values are keyed by their index, starting
+    /// from zero, and named values are keyed by their name.
+    pub fn variadic_map(&self) -> HashMap<String, Zval> {
+        self.variadic_zvals
+            .iter()
+            .enumerate()
+            .map(|(i, zv)| (i.to_string(), zv.shallow_clone()))
+            .chain(
+                self.named_zvals
+                    .iter()
+                    .map(|(name, zv)| (name.clone(), zv.shallow_clone())),
+            )
+            .collect()
+    }
+
+    /// Returns whether the argument is variadic.
+    pub(crate) fn variadic(&self) -> bool {
+        self.variadic
+    }
+
     /// Attempts to call the argument as a callable with a list of arguments to pass to the function.
     /// Note that a thrown exception inside the callable is not detectable, therefore you should
     /// check if the return value is valid rather than unwrapping. Returns a result containing the
@@ -110,38 +278,36 @@ impl<'a> Arg<'a> {
     }
 
     /// Returns the internal PHP argument info.
-    pub(crate) fn as_arg_info(&self) -> Result<ArgInfo> {
+    ///
+    /// # Parameters
+    ///
+    /// * `optional` - Whether the argument is optional. Optional nullable arguments without a
+    ///   default value default to `null`.
+    pub(crate) fn as_arg_info(&self, optional: bool) -> Result<ArgInfo> {
         Ok(ArgInfo {
             name: CString::new(self.name.as_str())?.into_raw(),
-            type_: ZendType::empty_from_type(
-                self._type,
-                self.as_ref,
-                self.variadic,
-                self.allow_null,
-            )
-            .ok_or(Error::InvalidCString)?,
+            type_: self
+                .expected_type()
+                .to_zend_type(self.as_ref, self.variadic)?,
             default_value: match &self.default_value {
                 Some(val) => CString::new(val.as_str())?.into_raw(),
+                None if optional && self.allow_null && !self.variadic => {
+                    CString::new("null")?.into_raw()
+                }
                 None => ptr::null(),
             },
         })
     }
 }
 
+/// Converts the type of the argument into the closest expected type used by PHP's argument
+/// errors. Unions without a matching expected type use the expected type of their first member,
+/// and types which do not correspond to any expected type, such as `mixed`, use `object`.
 impl From<Arg<'_>> for _zend_expected_type {
     fn from(arg: Arg) -> Self {
-        let err = match arg._type {
-            DataType::False | DataType::True => _zend_expected_type_Z_EXPECTED_BOOL,
-            DataType::Long => _zend_expected_type_Z_EXPECTED_LONG,
-            DataType::Double => _zend_expected_type_Z_EXPECTED_DOUBLE,
-            DataType::String => _zend_expected_type_Z_EXPECTED_STRING,
-            DataType::Array => _zend_expected_type_Z_EXPECTED_ARRAY,
-            DataType::Object(_) => _zend_expected_type_Z_EXPECTED_OBJECT,
-            DataType::Resource => _zend_expected_type_Z_EXPECTED_RESOURCE,
-            _ => unreachable!(),
-        };
+        let err = expected_type(&arg.ty).unwrap_or(_zend_expected_type_Z_EXPECTED_OBJECT);
 
-        if arg.allow_null {
+        if arg.allow_null || arg.ty.is_nullable() {
             err + 1
         } else {
             err
@@ -149,6 +315,94 @@ impl From<Arg<'_>> for _zend_expected_type {
     }
 }
 
+/// Returns the expected type matching a type declaration, ignoring whether it accepts `null`.
+///
+/// # Parameters
+///
+/// * `ty` - The type declaration.
+fn expected_type(ty: &TypeSpec) -> Option<_zend_expected_type> {
+    let members = match ty {
+        TypeSpec::Union(types) => types,
+        TypeSpec::Nullable(ty) => return expected_type(ty),
+        ty => {
+            return Some(match ty {
+                TypeSpec::Data(DataType::False | DataType::True | DataType::Bool) => {
+                    _zend_expected_type_Z_EXPECTED_BOOL
+                }
+                TypeSpec::Data(DataType::Long) => _zend_expected_type_Z_EXPECTED_LONG,
+                TypeSpec::Data(DataType::Double) => _zend_expected_type_Z_EXPECTED_DOUBLE,
+                TypeSpec::Data(DataType::String) => _zend_expected_type_Z_EXPECTED_STRING,
+                TypeSpec::Data(DataType::Array) => _zend_expected_type_Z_EXPECTED_ARRAY,
+                TypeSpec::Data(DataType::Resource) => _zend_expected_type_Z_EXPECTED_RESOURCE,
+                TypeSpec::Data(DataType::Callable) => _zend_expected_type_Z_EXPECTED_FUNC,
+                TypeSpec::Data(DataType::Object(_))
+                | TypeSpec::Class(_)
+                | TypeSpec::Static
+                | TypeSpec::Intersection(_) => _zend_expected_type_Z_EXPECTED_OBJECT,
+                TypeSpec::Iterable => _zend_expected_type_Z_EXPECTED_ITERABLE,
+                _ => return None,
+            })
+        }
+    };
+
+    let mut expected: Vec<_> = members
+        .iter()
+        .filter(|ty| **ty != TypeSpec::Data(DataType::Null))
+        .filter_map(expected_type)
+        .collect();
+    let first = expected.first().copied();
+    expected.sort_unstable();
+    expected.dedup();
+
+    #[allow(non_upper_case_globals)]
+    match expected[..] {
+        [ty] => Some(ty),
+        [_zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_DOUBLE] => {
+            Some(_zend_expected_type_Z_EXPECTED_NUMBER)
+        }
+        [_zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_STRING] => {
+            Some(_zend_expected_type_Z_EXPECTED_STRING_OR_LONG)
+        }
+        [_zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_ARRAY] => {
+            Some(_zend_expected_type_Z_EXPECTED_ARRAY_OR_LONG)
+        }
+        [_zend_expected_type_Z_EXPECTED_STRING, _zend_expected_type_Z_EXPECTED_ARRAY] => {
+            Some(_zend_expected_type_Z_EXPECTED_ARRAY_OR_STRING)
+        }
+        [_zend_expected_type_Z_EXPECTED_STRING, _zend_expected_type_Z_EXPECTED_OBJECT] => {
+            Some(_zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING)
+        }
+        _ => first,
+    }
+}
+
+/// Throws a `TypeError` for an argument of the active function, in the same format as PHP.
+///
+/// # Parameters
+///
+/// * `position` - The position of the argument, starting from 1.
+/// * `expected` - The type of the argument.
+/// * `given` - The value given for the argument.
+fn throw_type_error(position: usize, expected: &TypeSpec, given: Option<&Zval>) {
+    let given = match given {
+        // SAFETY: The zval is valid, and the returned type name is either static or the name of
+        // the class of the object, which outlives this function.
+        Some(zv) => unsafe { CStr::from_ptr(zend_zval_type_name(zv)) }
+            .to_string_lossy()
+            .into_owned(),
+        None => "null".into(),
+    };
+
+    let format = CString::new("%s").expect("Format string contains NUL-bytes");
+    let message = match CString::new(format!("must be of type {}, {} given", expected, given)) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+
+    // SAFETY: The format string takes a single string, which is given.
+    unsafe { zend_argument_type_error(position as u32, format.as_ptr(), message.as_ptr()) };
+}
+
 /// Internal argument information used by Zend.
 pub type ArgInfo = zend_internal_arg_info;
 
@@ -197,14 +451,19 @@ impl<'a, 'arg, 'zval> ArgParser<'a, 'arg, 'zval> {
     /// # Errors
     ///
     /// Returns an [`Error`] type if there were too many or too little arguments passed to the
-    /// function. The user has already been notified so you should break execution after seeing an
-    /// error type.
+    /// function, if a named argument was given for a parameter which was already given
+    /// positionally, or if a named argument did not match any of the parameters of a function
+    /// which is not variadic. The user has already been notified so you should break execution
+    /// after seeing an error type.
     pub fn parse(mut self) -> Result<()> {
         let num_args = unsafe { self.execute_data.This.u2.num_args };
-        let max_num_args = self.args.len() as u32;
+        let variadic = self.args.last().map(|arg| arg.variadic).unwrap_or(false);
+        let num_params = self.args.len() as u32 - variadic as u32;
+        // A variadic argument accepts any number of values.
+        let max_num_args = if variadic { u32::MAX } else { num_params };
         let min_num_args = match self.min_num_args {
             Some(n) => n,
-            None => max_num_args,
+            None => num_params,
         };
 
         if num_args < min_num_args || num_args > max_num_args {
@@ -214,10 +473,127 @@ impl<'a, 'arg, 'zval> ArgParser<'a, 'arg, 'zval> {
             return Err(Error::IncorrectArguments(num_args, min_num_args));
         }
 
+        let execute_data = self.execute_data;
+        let strict = execute_data.uses_strict_types();
         for (i, arg) in self.args.iter_mut().enumerate() {
-            arg.zval = unsafe { self.execute_data.zend_call_arg(i) };
+            arg.position = i;
+            arg.strict = strict;
+
+            if arg.variadic {
+                arg.variadic_zvals = (i..num_args as usize)
+                    .filter_map(|n| unsafe { execute_data.zend_call_arg(n) })
+                    .collect();
+            } else if i < num_args as usize {
+                // Optional arguments skipped by passing a later argument by name are undefined.
+                arg.zval = unsafe { execute_data.zend_call_arg(i) }
+                    .filter(|zv| zv.get_type() != DataType::Undef);
+            }
+        }
+
+        // SAFETY: The named arguments live as long as the call frame, the same as the positional
+        // arguments.
+        let named = execute_data
+            .extra_named_params()
+            .map(|ht| unsafe { &*(ht as *const HashTable) });
+
+        if let Some(named) = named {
+            for (_, name, zv) in named.iter() {
+                let name = match name {
+                    Some(name) => name,
+                    None => continue,
+                };
+
+                if let Some(arg) = self
+                    .args
+                    .iter_mut()
+                    .find(|arg| !arg.variadic && arg.name == name)
+                {
+                    if arg.zval.is_some() {
+                        let _ = throw(
+                            ClassEntry::error(),
+                            &format!("Named parameter ${} overwrites previous argument", name),
+                        );
+                        return Err(Error::DuplicateNamedParameter(name));
+                    }
+
+                    arg.zval = Some(zv);
+                    continue;
+                }
+
+                match self.args.last_mut().filter(|arg| arg.variadic) {
+                    Some(arg) => arg.named_zvals.push((name, zv)),
+                    None => {
+                        let _ = throw(
+                            ClassEntry::error(),
+                            &format!("Unknown named parameter ${}", name),
+                        );
+                        return Err(Error::UnknownNamedParameter(name));
+                    }
+                }
+            }
+        }
+
+        // Required arguments can only be missing when later arguments were given by name.
+        for (i, arg) in self.args.iter().enumerate().take(min_num_args as usize) {
+            if !arg.variadic && arg.zval.is_none() {
+                // SAFETY: Exported C function is safe, the format string is static and contains no
+                // format specifiers.
+                unsafe {
+                    zend_argument_error(
+                        ClassEntry::argument_count_error() as *const _ as *mut _,
+                        i as u32 + 1,
+                        b"not passed\0".as_ptr() as _,
+                    )
+                };
+
+                return Err(Error::IncorrectArguments(num_args, min_num_args));
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::expected_type;
+    use crate::{
+        bindings::{
+            _zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_NUMBER,
+            _zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING, _zend_expected_type_Z_EXPECTED_STRING,
+        },
+        php::{enums::DataType, types::type_spec::TypeSpec},
+    };
+
+    #[test]
+    fn test_union_expected_type() {
+        let ty = TypeSpec::union(vec![DataType::Double.into(), DataType::Long.into()]).nullable();
+        assert_eq!(
+            expected_type(&ty),
+            Some(_zend_expected_type_Z_EXPECTED_NUMBER)
+        );
+
+        let ty = TypeSpec::union(vec![TypeSpec::class("Foo"), DataType::String.into()]);
+        assert_eq!(
+            expected_type(&ty),
+            Some(_zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING)
+        );
+
+        let ty = TypeSpec::union(vec![
+            DataType::String.into(),
+            DataType::Long.into(),
+            DataType::Double.into(),
+        ]);
+        assert_eq!(
+            expected_type(&ty),
+            Some(_zend_expected_type_Z_EXPECTED_STRING)
+        );
+
+        let ty = TypeSpec::union(vec![DataType::Mixed.into(), DataType::Long.into()]);
+        assert_eq!(
+            expected_type(&ty),
+            Some(_zend_expected_type_Z_EXPECTED_LONG)
+        );
+        assert_eq!(expected_type(&DataType::Mixed.into()), None);
+    }
+}
@@ -7,18 +7,20 @@ use crate::{
 use std::{alloc::Layout, convert::TryInto, ffi::CString, fmt::Debug};
 
 use crate::bindings::{
-    zend_class_entry, zend_declare_class_constant, zend_declare_property,
-    zend_do_implement_interface, zend_register_internal_class_ex,
+    object_init_ex, zend_class_entry, zend_declare_class_constant, zend_declare_property,
+    zend_do_implement_interface, zend_register_internal_class_ex, zend_update_class_constants,
 };
 
 use super::{
+    execution_data::ExecutionData,
     flags::{ClassFlags, MethodFlags, PropertyFlags},
     function::FunctionEntry,
     globals::ExecutorGlobals,
     types::{
-        object::RegisteredClass,
+        callable::CallBuilder,
+        object::{ClassObject, RegisteredClass},
         string::ZendString,
-        zval::{IntoZval, Zval},
+        zval::{IntoZval, IntoZvalDyn, Zval},
     },
 };
 
@@ -46,6 +48,11 @@ impl ClassEntry {
         }
     }
 
+    /// Returns the name of the class.
+    pub fn name(&self) -> Option<&str> {
+        unsafe { self.name.as_ref() }.and_then(|name| name.as_str())
+    }
+
     /// Returns the class flags.
     pub fn flags(&self) -> ClassFlags {
         ClassFlags::from_bits_truncate(self.ce_flags)
@@ -78,15 +85,13 @@ impl ClassEntry {
                 }
             }
         } else {
-            loop {
-                let parent = match self.parent() {
-                    Some(parent) => parent,
-                    None => return false,
-                };
-
+            let mut class = self;
+            while let Some(parent) = class.parent() {
                 if parent == ce {
                     return true;
                 }
+
+                class = parent;
             }
         }
 
@@ -119,6 +124,181 @@ impl ClassEntry {
             Self::try_find(name.as_str()?)
         }
     }
+
+    /// Attempts to call a static method of the class, the same as `Class::name(...$params)` in
+    /// PHP. Returns a result containing the return value of the method. Use
+    /// [`CallBuilder::static_method`] to pass arguments by name or by reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the method.
+    /// * `params` - A list of parameters to call the method with.
+    pub fn try_call_static(&self, name: &str, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        let mut call = CallBuilder::static_method(self, name)?;
+        for param in params {
+            call = call.arg(param.as_zval(false)?);
+        }
+        call.call()
+    }
+}
+
+/// The context of a method call, giving access to the class the method was called on.
+///
+/// Static methods exported with the [`macro@crate::php_impl`] macro can take a parameter of this
+/// type, which is filled in by the macro rather than passed from PHP. As PHP uses late static
+/// binding, the called class may be a subclass of the class which declared the method, such as
+/// when calling `Sub::create()` where `create()` is declared on the parent of `Sub`. This allows
+/// static methods to behave like `new static` and `static::class` in PHP.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ext_php_rs::prelude::*;
+/// use ext_php_rs::php::{class::StaticContext, types::object::ClassObject};
+///
+/// #[php_class]
+/// #[derive(Default)]
+/// pub struct Model {
+///     id: i64,
+/// }
+///
+/// #[php_impl]
+/// impl Model {
+///     // `Model::find(5)` returns a `Model`, while `User::find(5)` returns a `User` if `User`
+///     // extends `Model`.
+///     pub fn find(ctx: StaticContext, id: i64) -> PhpResult<ClassObject<'static, Model>> {
+///         Ok(ctx.new_instance(Model { id })?)
+///     }
+/// }
+/// # #[php_module]
+/// # pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StaticContext<'a> {
+    called_scope: &'a ClassEntry,
+}
+
+impl<'a> StaticContext<'a> {
+    /// Creates a static context from the execution data of a method call. Returns [`None`] if
+    /// the function was not called in the scope of a class.
+    ///
+    /// # Parameters
+    ///
+    /// * `ex` - The execution data of the method call.
+    pub fn new(ex: &'a ExecutionData) -> Option<Self> {
+        Some(Self {
+            called_scope: ex.called_scope()?,
+        })
+    }
+
+    /// Returns the class the method was called on, which is the class referred to by `static`
+    /// in PHP.
+    pub fn called_scope(&self) -> &'a ClassEntry {
+        self.called_scope
+    }
+
+    /// Returns the name of the class the method was called on, which is the value of
+    /// `static::class` in PHP.
+    pub fn class_name(&self) -> Option<&'a str> {
+        self.called_scope.name()
+    }
+
+    /// Creates an instance of the called class holding the given Rust object. The PHP
+    /// constructor of the class is not called.
+    ///
+    /// # Parameters
+    ///
+    /// * `obj` - The Rust object the instance holds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidScope`] if the called class is not the class of `T` or a PHP class
+    /// inheriting from it, or [`Error::Instantiation`] if the called class is abstract. Classes
+    /// exported from Rust which have `T` as their `#[parent]` hold a different type, and
+    /// therefore cannot be instantiated with a `T`. Returns [`Error::Exception`] if the constant
+    /// expressions in the property defaults of the called class could not be evaluated, in which
+    /// case PHP has thrown an exception.
+    pub fn new_instance<T: RegisteredClass>(&self, obj: T) -> Result<ClassObject<'static, T>> {
+        let meta = T::get_metadata();
+        if !meta.has_ce() || !self.called_scope.instance_of(meta.ce()) {
+            return Err(Error::InvalidScope);
+        }
+
+        // PHP classes inherit the object constructor of their parent, while Rust classes
+        // inheriting from `T` allocate objects holding their own type. The stored pointers are
+        // compared, which are identical when inherited.
+        let create_object = |ce: &ClassEntry| {
+            unsafe { ce.__bindgen_anon_2.create_object }.map(|func| func as usize)
+        };
+        if create_object(self.called_scope) != create_object(meta.ce()) {
+            return Err(Error::InvalidScope);
+        }
+        self.check_instantiable()?;
+
+        // Property defaults of PHP classes may be constant expressions, which are evaluated the
+        // first time the class is instantiated, as done by `object_init_ex`.
+        let ce = self.called_scope as *const ClassEntry as *mut ClassEntry;
+        if unsafe { zend_update_class_constants(ce) } < 0 {
+            return Err(Error::Exception);
+        }
+
+        let ptr = ZendClassObject::new_ptr_with_ce(Some(self.called_scope), Some(obj));
+        // SAFETY: The object was just allocated, and is released when the class object is
+        // dropped. The object holds a `T` as the called class inherits from the class of `T`.
+        unsafe {
+            ClassObject::from_zend_obj(
+                ptr.as_mut()
+                    .expect("Failed to allocate memory for class object.")
+                    .get_mut_zend_obj(),
+                true,
+            )
+        }
+        .ok_or(Error::InvalidScope)
+    }
+
+    /// Creates an instance of the called class, initializing the object the same way as `new`
+    /// in PHP, without calling the constructor. Unlike [`StaticContext::new_instance`], this
+    /// works for any class, including classes not exported from Rust.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Instantiation`] if the called class is abstract, in which case PHP
+    /// has thrown an exception.
+    pub fn new_object(&self) -> Result<Zval> {
+        let mut zv = Zval::new();
+        let result = unsafe {
+            object_init_ex(
+                &mut zv,
+                self.called_scope as *const ClassEntry as *mut ClassEntry,
+            )
+        };
+
+        if result < 0 {
+            Err(self.instantiation_error())
+        } else {
+            Ok(zv)
+        }
+    }
+
+    /// Checks that the called class can be instantiated.
+    fn check_instantiable(&self) -> Result<()> {
+        if self.called_scope.flags().intersects(
+            ClassFlags::Interface
+                | ClassFlags::Trait
+                | ClassFlags::ImplicitAbstractClass
+                | ClassFlags::Abstract,
+        ) {
+            Err(self.instantiation_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn instantiation_error(&self) -> Error {
+        Error::Instantiation(self.class_name().unwrap_or_default().to_string())
+    }
 }
 
 /// Builds a class to be exported as a PHP class.
@@ -270,9 +450,11 @@ impl ClassBuilder {
     /// when creating the builder.
     pub fn object_override<T: RegisteredClass>(mut self) -> Self {
         unsafe extern "C" fn create_object<T: RegisteredClass>(
-            _: *mut ClassEntry,
+            ce: *mut ClassEntry,
         ) -> *mut ZendObject {
-            let ptr = ZendClassObject::<T>::new_ptr(None);
+            // `ce` is the class being instantiated, which is a subclass of `T` when the object
+            // is created for a class inheriting from `T`.
+            let ptr = ZendClassObject::<T>::new_ptr_with_ce(ce.as_ref(), None);
             (*ptr).get_mut_zend_obj()
         }
 
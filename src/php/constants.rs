@@ -1,13 +1,22 @@
-//! Types relating to registering constants in PHP.
+//! Types relating to registering and looking up constants in PHP.
 
-use std::ffi::CString;
+use std::{ffi::CString, ptr};
 
-use super::flags::GlobalConstantFlags;
+use super::{
+    flags::{GlobalConstantFlags, ZendResult},
+    globals::ExecutorGlobals,
+    types::{
+        string::ZendString,
+        zval::{IntoZval, Zval},
+    },
+};
 use crate::bindings::{
-    zend_register_bool_constant, zend_register_double_constant, zend_register_long_constant,
-    zend_register_string_constant,
+    ext_php_rs_zval_persist, ext_php_rs_zval_persist_release, zend_constant, zend_get_constant_ex,
+    zend_register_bool_constant, zend_register_constant, zend_register_double_constant,
+    zend_register_long_constant, zend_register_string_constant, PHP_USER_CONSTANT,
+    ZEND_FETCH_CLASS_SILENT,
 };
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 
 pub trait IntoConst: Sized {
     /// Registers a global module constant in PHP, with the value as the content of self.
@@ -157,3 +166,163 @@ into_const_num!(i32, zend_register_long_constant);
 into_const_num!(i64, zend_register_long_constant);
 into_const_num!(f32, zend_register_double_constant);
 into_const_num!(f64, zend_register_double_constant);
+
+impl<T> IntoConst for Vec<T>
+where
+    T: IntoZval + Clone,
+{
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        register_zval(name, self.clone().into_zval(false)?, flags, module_number)
+    }
+}
+
+impl<T> IntoConst for &[T]
+where
+    T: IntoZval + Clone,
+{
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        self.to_vec()
+            .register_constant_flags(name, module_number, flags)
+    }
+}
+
+/// Registers `null` when the value is [`None`].
+impl<T> IntoConst for Option<T>
+where
+    T: IntoConst,
+{
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        match self {
+            Some(val) => val.register_constant_flags(name, module_number, flags),
+            None => register_zval(name, Zval::new(), flags, module_number),
+        }
+    }
+}
+
+/// Registers a constant with the value of a zval. The value is made persistent if the constant
+/// is persistent.
+fn register_zval(
+    name: &str,
+    mut value: Zval,
+    flags: GlobalConstantFlags,
+    module_number: i32,
+) -> Result<()> {
+    let persistent = flags.contains(GlobalConstantFlags::Persistent);
+    if persistent {
+        // SAFETY: The zval is valid and owned.
+        unsafe { ext_php_rs_zval_persist(&mut value) };
+    }
+
+    let mut constant = zend_constant {
+        value,
+        name: ZendString::new(name, persistent)?.into_inner(),
+    };
+    // Translation of the `ZEND_CONSTANT_SET_FLAGS` macro.
+    constant.value.u2.constant_flags = (flags.bits() & 0xff) | ((module_number as u32) << 8);
+
+    // SAFETY: The name and value are moved into the constant table on success. On failure, PHP
+    // releases the name, and the value only if the constant is not persistent.
+    let result = unsafe { zend_register_constant(&mut constant) };
+    if persistent && result != ZendResult::Success.bits() {
+        // SAFETY: The value was made persistent above and is no longer used.
+        unsafe { ext_php_rs_zval_persist_release(&mut constant.value) };
+    }
+    constant.value.release();
+
+    if result == ZendResult::Success.bits() {
+        Ok(())
+    } else {
+        Err(Error::ConstantExists(name.to_string()))
+    }
+}
+
+/// Looks up the value of a constant, the same as `constant($name)` in PHP. Returns [`None`] if
+/// the constant does not exist.
+///
+/// # Parameters
+///
+/// * `name` - The name of the constant, such as `PHP_VERSION`, or of a class constant, such as
+///   `PDO::ATTR_ERRMODE`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::constants;
+///
+/// let version = constants::get("PHP_VERSION");
+/// let errmode = constants::get("PDO::ATTR_ERRMODE").and_then(|val| val.long());
+/// ```
+pub fn get(name: &str) -> Option<Zval> {
+    let mut name = ZendString::new(name, false).ok()?;
+
+    // SAFETY: The name is valid, and the returned value is copied before any other constant is
+    // registered.
+    unsafe {
+        zend_get_constant_ex(
+            name.as_mut_zend_str(),
+            ptr::null_mut(),
+            ZEND_FETCH_CLASS_SILENT,
+        )
+        .as_ref()
+    }
+    .map(Zval::shallow_clone)
+}
+
+/// Defines a constant for the remainder of the current request, the same as
+/// `define($name, $value)` in PHP. Constants registered when the module starts up should be
+/// registered with [`IntoConst`] instead.
+///
+/// # Parameters
+///
+/// * `name` - The name of the constant.
+/// * `value` - The value of the constant.
+///
+/// # Errors
+///
+/// Returns [`Error::ClassConstant`] if the name is the name of a class constant, such as
+/// `Foo::BAR`, or [`Error::ConstantExists`] if a global constant with the same name already
+/// exists.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::constants;
+///
+/// constants::define("REQUEST_ID", "0af7651916cd43dd").unwrap();
+/// ```
+pub fn define(name: &str, value: impl IntoZval) -> Result<()> {
+    if name.contains("::") {
+        return Err(Error::ClassConstant(name.to_string()));
+    }
+
+    // Only the global constant table is checked, as `get` could autoload classes and throw.
+    if ExecutorGlobals::get()
+        .constant_table()
+        .and_then(|table| table.get(name))
+        .is_some()
+    {
+        return Err(Error::ConstantExists(name.to_string()));
+    }
+
+    register_zval(
+        name,
+        value.into_zval(false)?,
+        GlobalConstantFlags::CaseSensitive,
+        PHP_USER_CONSTANT as _,
+    )
+}
@@ -6,9 +6,9 @@ use super::{class::ClassEntry, types::object::RegisteredClass};
 use crate::{
     bindings::{
         zend_ce_argument_count_error, zend_ce_arithmetic_error, zend_ce_compile_error,
-        zend_ce_division_by_zero_error, zend_ce_error_exception, zend_ce_exception,
+        zend_ce_division_by_zero_error, zend_ce_error, zend_ce_error_exception, zend_ce_exception,
         zend_ce_parse_error, zend_ce_throwable, zend_ce_type_error, zend_ce_unhandled_match_error,
-        zend_ce_value_error, zend_throw_exception_ex,
+        zend_ce_value_error, zend_error, zend_throw_exception_ex,
     },
     errors::{Error, Result},
     php::flags::ClassFlags,
@@ -141,6 +141,25 @@ pub fn throw_with_code(ex: &ClassEntry, code: i32, message: &str) -> Result<()>
     Ok(())
 }
 
+/// Raises a PHP error which is not an exception, such as a warning or deprecation notice. The
+/// error is passed to the error handler, which may turn it into an exception.
+///
+/// # Parameters
+///
+/// * `level` - The level of the error, such as `E_WARNING`.
+/// * `message` - The message of the error.
+pub(crate) fn error(level: u32, message: &str) -> Result<()> {
+    // SAFETY: The format string takes a single string, which is given.
+    unsafe {
+        zend_error(
+            level as _,
+            CString::new("%s")?.as_ptr(),
+            CString::new(message)?.as_ptr(),
+        )
+    };
+    Ok(())
+}
+
 // SAFETY: All default exceptions have been initialized by the time we should use these (in the module
 // startup function). Note that they are not valid during the module init function, but rather than
 // wrapping everything
@@ -156,6 +175,11 @@ impl ClassEntry {
         unsafe { zend_ce_exception.as_ref() }.unwrap()
     }
 
+    /// Returns the base `Error` class.
+    pub fn error() -> &'static Self {
+        unsafe { zend_ce_error.as_ref() }.unwrap()
+    }
+
     /// Returns the base `ErrorException` class.
     pub fn error_exception() -> &'static Self {
         unsafe { zend_ce_error_exception.as_ref() }.unwrap()
@@ -1,13 +1,22 @@
 //! Functions for interacting with the execution data passed to PHP functions\
 //! introduced in Rust.
 
+use std::convert::TryInto;
+
 use crate::{
-    bindings::{zend_execute_data, ZEND_MM_ALIGNMENT, ZEND_MM_ALIGNMENT_MASK},
+    bindings::{
+        zend_execute_data, zend_fetch_debug_backtrace, zend_get_executed_filename_ex,
+        zend_get_executed_lineno, zend_op_array, ZEND_ACC_STRICT_TYPES,
+        ZEND_CALL_HAS_EXTRA_NAMED_PARAMS, ZEND_MM_ALIGNMENT, ZEND_MM_ALIGNMENT_MASK,
+    },
     errors::{Error, Result},
 };
 
+use super::class::ClassEntry;
+use super::globals::ExecutorGlobals;
 use super::types::{
-    object::{ClassObject, RegisteredClass, ZendClassObject, ZendObject},
+    array::HashTable,
+    object::{ClassObject, RegisteredClass, ZendObject},
     zval::Zval,
 };
 
@@ -26,10 +35,9 @@ impl ExecutionData {
     /// that:
     ///
     /// 1. Contains an object.
-    /// 2. The object was originally derived from `T`.
+    /// 2. The object was originally derived from `T`, or from a class inheriting from `T`.
     pub unsafe fn get_object<T: RegisteredClass>(&self) -> Option<ClassObject<'static, T>> {
-        let ptr = ZendClassObject::<T>::from_zend_obj_ptr(self.This.object()?)?;
-        Some(ClassObject::from_zend_class_object(ptr, false))
+        ClassObject::from_zend_obj(self.This.object()?, false)
     }
 
     /// Attempts to retrieve the 'this' object, which can be used in class methods
@@ -38,6 +46,170 @@ impl ExecutionData {
         unsafe { self.This.value.obj.as_mut() }.ok_or(Error::InvalidScope)
     }
 
+    /// Returns the class the function was called on, which is the class of `$this` for instance
+    /// methods, or the class named in the call for static methods. This is the class referred to
+    /// by `static::class` in PHP, and may be a subclass of the class which declared the method.
+    ///
+    /// Returns [`None`] if the function was not called in the scope of a class.
+    pub fn called_scope(&self) -> Option<&ClassEntry> {
+        match self.This.object() {
+            Some(obj) => unsafe { obj.ce.as_ref() },
+            None => unsafe { self.This.value.ce.as_ref() },
+        }
+    }
+
+    /// Returns the name of the function being executed, or [`None`] if the execution data is not
+    /// for a function call, such as the main script or an `include`.
+    pub fn function_name(&self) -> Option<&str> {
+        // SAFETY: The function and its name are either null or valid.
+        unsafe { self.func.as_ref()?.common.function_name.as_ref()?.as_str() }
+    }
+
+    /// Returns the class which declared the function being executed, or [`None`] if the function
+    /// is not a method. See [`called_scope`](Self::called_scope) for the class the method was
+    /// called on.
+    pub fn scope(&self) -> Option<&ClassEntry> {
+        unsafe { self.func.as_ref()?.common.scope.as_ref() }
+    }
+
+    /// Returns the file containing the code being executed, or [`None`] if an internal function
+    /// is being executed.
+    pub fn file(&self) -> Option<&str> {
+        unsafe { self.op_array()?.filename.as_ref()?.as_str() }
+    }
+
+    /// Returns the line of the code being executed, or [`None`] if an internal function is being
+    /// executed.
+    pub fn line(&self) -> Option<u32> {
+        self.op_array()?;
+        unsafe { self.opline.as_ref() }.map(|opline| opline.lineno)
+    }
+
+    /// Returns the execution data of the nearest function written in PHP which called this
+    /// function, directly or through internal functions. The file and line of the call can be
+    /// retrieved from the returned execution data.
+    pub fn caller(&self) -> Option<&ExecutionData> {
+        let mut ex = unsafe { self.prev_execute_data.as_ref() };
+        while let Some(prev) = ex {
+            if prev.op_array().is_some() {
+                break;
+            }
+            ex = unsafe { prev.prev_execute_data.as_ref() };
+        }
+        ex
+    }
+
+    /// Returns the execution data of the function currently being executed, or [`None`] if PHP
+    /// is not executing code.
+    pub fn current() -> Option<&'static ExecutionData> {
+        unsafe { ExecutorGlobals::get().current_execute_data.as_ref() }
+    }
+
+    /// Returns the file of the PHP code currently being executed, or [`None`] if PHP is not
+    /// executing code. When called from an internal function, this is the file the function was
+    /// called from.
+    pub fn executed_file() -> Option<String> {
+        unsafe { zend_get_executed_filename_ex().as_ref() }
+            .and_then(|file| file.as_str())
+            .map(|file| file.to_string())
+    }
+
+    /// Returns the line of the PHP code currently being executed, or [`None`] if PHP is not
+    /// executing code. When called from an internal function, this is the line the function was
+    /// called from.
+    pub fn executed_line() -> Option<u32> {
+        match unsafe { zend_get_executed_lineno() } {
+            0 => None,
+            line => Some(line),
+        }
+    }
+
+    /// Returns the backtrace of the code currently being executed, starting with the function
+    /// currently being executed. This is equivalent to `debug_backtrace()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - The maximum number of frames to return, or zero to return all frames.
+    pub fn backtrace(limit: u32) -> Vec<BacktraceFrame> {
+        let mut trace = Zval::new();
+        unsafe { zend_fetch_debug_backtrace(&mut trace, 0, 0, limit as _) };
+
+        trace
+            .array()
+            .map(|frames| {
+                frames
+                    .values()
+                    .filter_map(Zval::array)
+                    .map(BacktraceFrame::from_ht)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of arguments given to the function.
+    ///
+    /// Translation of macro `ZEND_CALL_NUM_ARGS(call)`.
+    pub fn num_args(&self) -> u32 {
+        unsafe { self.This.u2.num_args }
+    }
+
+    /// Returns the positional arguments given to the function, including those which do not
+    /// match a parameter of the function.
+    pub fn args(&self) -> Vec<&Zval> {
+        let num_args = self.num_args() as usize;
+
+        // The arguments of user functions which do not match a parameter are moved after the
+        // compiled and temporary variables of the function.
+        let (num_params, extra_offset) = match self.op_array() {
+            Some(op_array) => (
+                (op_array.num_args as usize).min(num_args),
+                op_array.last_var as usize + op_array.T as usize,
+            ),
+            None => (num_args, num_args),
+        };
+
+        (0..num_params)
+            .chain(extra_offset..extra_offset + num_args - num_params)
+            .filter_map(|n| unsafe { self.zend_call_arg(n) })
+            .collect()
+    }
+
+    /// Returns the op array of the function being executed if it is user code.
+    fn op_array(&self) -> Option<&zend_op_array> {
+        let func = unsafe { self.func.as_ref() }?;
+
+        // Translation of macro `ZEND_USER_CODE(type)`.
+        if unsafe { func.type_ } & 1 == 0 {
+            Some(unsafe { &func.op_array })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the named arguments given to the function which do not match a parameter of the
+    /// function, keyed by name. These are only present when the function is variadic, otherwise
+    /// PHP rejects unknown named arguments before the function is called.
+    pub fn extra_named_params(&self) -> Option<&HashTable> {
+        if unsafe { self.This.u1.type_info } & ZEND_CALL_HAS_EXTRA_NAMED_PARAMS == 0 {
+            return None;
+        }
+
+        unsafe { self.extra_named_params.as_ref() }
+    }
+
+    /// Returns whether the arguments given to the function must follow strict typing rules, which
+    /// is the case when the caller was declared with `declare(strict_types=1)`.
+    ///
+    /// Translation of macro `ZEND_ARG_USES_STRICT_TYPES()`, using this execution data as the
+    /// current execution data.
+    pub fn uses_strict_types(&self) -> bool {
+        // SAFETY: The previous execution data and its function are either null or valid.
+        unsafe { self.prev_execute_data.as_ref() }
+            .and_then(|prev| unsafe { prev.func.as_ref() })
+            .map(|func| unsafe { func.common.fn_flags } & ZEND_ACC_STRICT_TYPES != 0)
+            .unwrap_or(false)
+    }
+
     /// Translation of macro `ZEND_CALL_ARG(call, n)`
     /// zend_compile.h:578
     #[doc(hidden)]
@@ -71,6 +243,48 @@ impl ExecutionData {
     }
 }
 
+/// A frame of a backtrace, describing a function call. See [`ExecutionData::backtrace`].
+#[derive(Debug)]
+pub struct BacktraceFrame {
+    /// The name of the function called.
+    pub function: Option<String>,
+    /// The name of the class of the method called.
+    pub class: Option<String>,
+    /// Whether the method called was static.
+    pub is_static: bool,
+    /// The file the function was called from, which is not present for functions called by
+    /// internal functions.
+    pub file: Option<String>,
+    /// The line the function was called from, which is not present for functions called by
+    /// internal functions.
+    pub line: Option<u32>,
+    /// The arguments given to the function.
+    pub args: Vec<Zval>,
+}
+
+impl BacktraceFrame {
+    /// Creates a frame from an element of the array returned by `debug_backtrace()`.
+    fn from_ht(frame: &HashTable) -> Self {
+        let string = |key| frame.get(key).and_then(Zval::string);
+
+        Self {
+            function: string("function"),
+            class: string("class"),
+            is_static: frame.get("type").and_then(Zval::str) == Some("::"),
+            file: string("file"),
+            line: frame
+                .get("line")
+                .and_then(Zval::long)
+                .and_then(|line| line.try_into().ok()),
+            args: frame
+                .get("args")
+                .and_then(Zval::array)
+                .map(|args| args.values().map(Zval::shallow_clone).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ExecutionData;
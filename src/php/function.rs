@@ -3,12 +3,12 @@
 use std::{ffi::CString, mem, os::raw::c_char, ptr};
 
 use crate::errors::Result;
-use crate::{bindings::zend_function_entry, errors::Error};
+use crate::bindings::zend_function_entry;
 
 use super::{
     args::{Arg, ArgInfo},
-    enums::DataType,
     execution_data::ExecutionData,
+    types::type_spec::TypeSpec,
     types::zval::Zval,
     types::ZendType,
 };
@@ -47,7 +47,7 @@ pub struct FunctionBuilder<'a> {
     function: FunctionEntry,
     args: Vec<Arg<'a>>,
     n_req: Option<usize>,
-    retval: Option<DataType>,
+    retval: Option<TypeSpec>,
     ret_as_ref: bool,
     ret_as_null: bool,
 }
@@ -110,11 +110,13 @@ impl<'a> FunctionBuilder<'a> {
     ///
     /// # Parameters
     ///
-    /// * `type_` - The return type of the function.
+    /// * `type_` - The return type of the function, either a [`DataType`] or a [`TypeSpec`].
     /// * `as_ref` - Whether the fucntion returns a reference.
     /// * `allow_null` - Whether the function return value is nullable.
-    pub fn returns(mut self, type_: DataType, as_ref: bool, allow_null: bool) -> Self {
-        self.retval = Some(type_);
+    ///
+    /// [`DataType`]: crate::php::enums::DataType
+    pub fn returns<T: Into<TypeSpec>>(mut self, type_: T, as_ref: bool, allow_null: bool) -> Self {
+        self.retval = Some(type_.into());
         self.ret_as_ref = as_ref;
         self.ret_as_null = allow_null;
         self
@@ -126,14 +128,19 @@ impl<'a> FunctionBuilder<'a> {
     pub fn build(mut self) -> Result<FunctionEntry> {
         let mut args = Vec::with_capacity(self.args.len() + 1);
 
+        // A variadic argument is never required.
+        let n_req = self
+            .n_req
+            .unwrap_or_else(|| self.args.iter().filter(|arg| !arg.variadic()).count());
+
         // argument header, retval etc
         args.push(ArgInfo {
-            name: self.n_req.unwrap_or(self.args.len()) as *const i8,
+            name: n_req as *const i8,
             type_: match self.retval {
-                Some(retval) => {
-                    ZendType::empty_from_type(retval, self.ret_as_ref, false, self.ret_as_null)
-                        .ok_or(Error::InvalidCString)?
+                Some(retval) if self.ret_as_null => {
+                    retval.nullable().to_zend_type(self.ret_as_ref, false)?
                 }
+                Some(retval) => retval.to_zend_type(self.ret_as_ref, false)?,
                 None => ZendType::empty(false, false),
             },
             default_value: ptr::null(),
@@ -143,7 +150,8 @@ impl<'a> FunctionBuilder<'a> {
         args.extend(
             self.args
                 .iter()
-                .map(|arg| arg.as_arg_info())
+                .enumerate()
+                .map(|(i, arg)| arg.as_arg_info(i >= n_req))
                 .collect::<Result<Vec<_>>>()?,
         );
 
@@ -1,8 +1,13 @@
-//! Types related to the PHP executor globals.
+//! Types related to the PHP executor and SAPI globals.
 
-use crate::bindings::{_zend_executor_globals, ext_php_rs_executor_globals};
+use std::ffi::CStr;
 
-use super::types::array::HashTable;
+use crate::bindings::{
+    _sapi_globals_struct, _zend_executor_globals, ext_php_rs_executor_globals,
+    ext_php_rs_sapi_globals,
+};
+
+use super::types::{array::HashTable, long::ZendLong};
 
 /// Stores global variables used in the PHP executor.
 pub type ExecutorGlobals = _zend_executor_globals;
@@ -20,4 +25,78 @@ impl ExecutorGlobals {
     pub fn class_table(&self) -> Option<&HashTable> {
         unsafe { self.class_table.as_ref() }
     }
+
+    /// Attempts to retrieve the global constant hash table.
+    pub fn constant_table(&self) -> Option<&HashTable> {
+        unsafe { self.zend_constants.as_ref() }
+    }
+
+    /// Returns the global symbol table, containing the global variables of the current request.
+    pub fn symbol_table(&self) -> &HashTable {
+        &self.symbol_table
+    }
+}
+
+/// Stores global variables used by the SAPI (Server API), such as the information about the
+/// current request and the state of the response.
+pub type SapiGlobals = _sapi_globals_struct;
+
+impl SapiGlobals {
+    /// Returns a static reference to the PHP SAPI globals.
+    pub fn get() -> &'static Self {
+        // SAFETY: PHP SAPI globals are statically declared therefore should never return an
+        // invalid pointer.
+        unsafe { ext_php_rs_sapi_globals().as_ref() }.expect("Static SAPI globals were invalid")
+    }
+
+    /// Returns the method of the current request, such as `GET`. Returns [`None`] if there is no
+    /// request method, such as when running from the command line.
+    pub fn request_method(&self) -> Option<&str> {
+        c_str(self.request_info.request_method)
+    }
+
+    /// Returns the URI of the current request.
+    pub fn request_uri(&self) -> Option<&str> {
+        c_str(self.request_info.request_uri)
+    }
+
+    /// Returns the query string of the current request, without the leading `?`.
+    pub fn query_string(&self) -> Option<&str> {
+        c_str(self.request_info.query_string)
+    }
+
+    /// Returns the content type of the body of the current request.
+    pub fn content_type(&self) -> Option<&str> {
+        c_str(self.request_info.content_type)
+    }
+
+    /// Returns the length of the body of the current request, as given by the client.
+    pub fn content_length(&self) -> ZendLong {
+        self.request_info.content_length
+    }
+
+    /// Returns the path of the script being executed.
+    pub fn path_translated(&self) -> Option<&str> {
+        c_str(self.request_info.path_translated)
+    }
+
+    /// Returns whether the headers of the response have been sent, after which headers can no
+    /// longer be modified.
+    pub fn headers_sent(&self) -> bool {
+        self.headers_sent != 0
+    }
+
+    /// Returns the HTTP status code of the response.
+    pub fn response_code(&self) -> i32 {
+        self.sapi_headers.http_response_code
+    }
+}
+
+/// Converts a string from the SAPI globals into a [`str`].
+fn c_str<'a>(ptr: *const std::os::raw::c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+    }
 }
@@ -0,0 +1,213 @@
+//! Control over the headers and status code of the response sent by the SAPI (Server API), the
+//! same as `header()`, `header_remove()` and `http_response_code()` in PHP.
+//!
+//! Headers can only be modified until the response starts being sent, which happens when the
+//! script first outputs data that is not buffered. Functions modifying the headers after this
+//! return [`Error::HeadersSent`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ext_php_rs::php::headers;
+//!
+//! pub fn redirect(location: &str) -> ext_php_rs::errors::Result<()> {
+//!     headers::set(&format!("Location: {}", location))?;
+//!     headers::set_response_code(302)
+//! }
+//!
+//! pub fn disable_cache() -> ext_php_rs::errors::Result<()> {
+//!     headers::set("Cache-Control: no-store")?;
+//!     headers::remove("ETag")
+//! }
+//! ```
+
+use std::{ffi::c_void, mem, slice};
+
+use crate::{
+    bindings::{
+        ext_php_rs_sapi_globals, sapi_header_line, sapi_header_op, sapi_header_op_enum,
+        sapi_header_op_enum_SAPI_HEADER_ADD, sapi_header_op_enum_SAPI_HEADER_DELETE,
+        sapi_header_op_enum_SAPI_HEADER_DELETE_ALL, sapi_header_op_enum_SAPI_HEADER_REPLACE,
+        sapi_header_op_enum_SAPI_HEADER_SET_STATUS, sapi_header_struct,
+    },
+    errors::{Error, Result},
+    php::{flags::ZendResult, globals::SapiGlobals, types::zval::Zval},
+};
+
+/// Sets a header of the response, replacing any headers with the same name. The same as
+/// `header($header)` in PHP.
+///
+/// # Parameters
+///
+/// * `header` - The header, such as `Content-Type: application/json`.
+///
+/// # Errors
+///
+/// Returns [`Error::HeadersSent`] if the headers have already been sent, or
+/// [`Error::InvalidHeader`] if the header is invalid, such as when it contains a new line.
+pub fn set(header: &str) -> Result<()> {
+    header_line_op(sapi_header_op_enum_SAPI_HEADER_REPLACE, header)
+}
+
+/// Adds a header to the response, keeping any headers with the same name. The same as
+/// `header($header, false)` in PHP.
+///
+/// # Parameters
+///
+/// * `header` - The header, such as `Set-Cookie: theme=dark`.
+///
+/// # Errors
+///
+/// Returns [`Error::HeadersSent`] if the headers have already been sent, or
+/// [`Error::InvalidHeader`] if the header is invalid, such as when it contains a new line.
+pub fn add(header: &str) -> Result<()> {
+    header_line_op(sapi_header_op_enum_SAPI_HEADER_ADD, header)
+}
+
+/// Removes the headers with the given name from the response. The same as
+/// `header_remove($name)` in PHP.
+///
+/// # Parameters
+///
+/// * `name` - The name of the header, which is case-insensitive.
+///
+/// # Errors
+///
+/// Returns [`Error::HeadersSent`] if the headers have already been sent.
+pub fn remove(name: &str) -> Result<()> {
+    header_line_op(sapi_header_op_enum_SAPI_HEADER_DELETE, name)
+}
+
+/// Removes all headers from the response. The same as `header_remove()` in PHP.
+///
+/// # Errors
+///
+/// Returns [`Error::HeadersSent`] if the headers have already been sent.
+pub fn clear() -> Result<()> {
+    header_op(
+        sapi_header_op_enum_SAPI_HEADER_DELETE_ALL,
+        std::ptr::null_mut(),
+        "",
+    )
+}
+
+/// Returns the headers of the response which have been set so far, such as
+/// `Content-Type: text/html`. The same as `headers_list()` in PHP.
+pub fn list() -> Vec<String> {
+    let headers = &SapiGlobals::get().sapi_headers.headers;
+    let mut list = Vec::with_capacity(headers.count as _);
+    let mut element = headers.head;
+
+    // SAFETY: The elements of the list are valid until the list is modified, and contain a
+    // header struct in place of the data.
+    while let Some(el) = unsafe { element.as_ref() } {
+        let header = unsafe { &*(el.data.as_ptr() as *const sapi_header_struct) };
+        if !header.header.is_null() {
+            let bytes = unsafe {
+                slice::from_raw_parts(header.header as *const u8, header.header_len as _)
+            };
+            list.push(String::from_utf8_lossy(bytes).into_owned());
+        }
+        element = el.next;
+    }
+
+    list
+}
+
+/// Returns the HTTP status code of the response. The same as `http_response_code()` in PHP.
+pub fn response_code() -> i32 {
+    SapiGlobals::get().response_code()
+}
+
+/// Sets the HTTP status code of the response. The same as `http_response_code($code)` in PHP.
+///
+/// # Parameters
+///
+/// * `code` - The status code, such as `404`.
+///
+/// # Errors
+///
+/// Returns [`Error::HeadersSent`] if the headers have already been sent.
+pub fn set_response_code(code: i32) -> Result<()> {
+    header_op(
+        sapi_header_op_enum_SAPI_HEADER_SET_STATUS,
+        code as isize as *mut c_void,
+        "",
+    )
+}
+
+/// Returns whether the headers of the response have been sent. The same as `headers_sent()` in
+/// PHP.
+pub fn sent() -> bool {
+    SapiGlobals::get().headers_sent()
+}
+
+/// Registers a function to be called just before the headers are sent, which can still modify
+/// the headers. Replaces any function registered previously, including from PHP. The same as
+/// `header_register_callback($callback)` in PHP.
+///
+/// The callback can be a [`Closure`](crate::php::types::closure::Closure) converted into a
+/// zval, or any other value callable from PHP.
+///
+/// # Parameters
+///
+/// * `callback` - The function to call.
+///
+/// # Errors
+///
+/// Returns [`Error::Callable`] if the value is not callable, or [`Error::HeadersSent`] if the
+/// headers have already been sent.
+pub fn register_callback(callback: Zval) -> Result<()> {
+    if !callback.is_callable() {
+        return Err(Error::Callable);
+    }
+    can_modify()?;
+
+    // SAFETY: The SAPI globals are valid for the lifetime of the process, and are not borrowed
+    // elsewhere while the callback is replaced.
+    let globals =
+        unsafe { ext_php_rs_sapi_globals().as_mut() }.expect("Static SAPI globals were invalid");
+
+    // Releases the previous callback, if any.
+    drop(mem::replace(&mut globals.callback_func, callback));
+    globals.fci_cache = unsafe { mem::zeroed() };
+
+    Ok(())
+}
+
+/// Returns an error if the headers can no longer be modified, following the check done by
+/// `sapi_header_op`.
+fn can_modify() -> Result<()> {
+    let globals = SapiGlobals::get();
+    if globals.headers_sent() && !globals.request_info.no_headers {
+        Err(Error::HeadersSent)
+    } else {
+        Ok(())
+    }
+}
+
+/// Performs a header operation taking a header line.
+fn header_line_op(op: sapi_header_op_enum, line: &str) -> Result<()> {
+    let mut header = sapi_header_line {
+        line: line.as_ptr() as _,
+        line_len: line.len() as _,
+        response_code: 0,
+    };
+    header_op(
+        op,
+        &mut header as *mut sapi_header_line as *mut c_void,
+        line,
+    )
+}
+
+/// Performs a header operation, returning an error naming the given header if it fails.
+fn header_op(op: sapi_header_op_enum, arg: *mut c_void, header: &str) -> Result<()> {
+    can_modify()?;
+
+    // SAFETY: The argument is of the type expected by the operation, and is only read.
+    if unsafe { sapi_header_op(op, arg) } == ZendResult::Success.bits() {
+        Ok(())
+    } else {
+        Err(Error::InvalidHeader(header.to_string()))
+    }
+}
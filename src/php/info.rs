@@ -0,0 +1,293 @@
+//! Builder for the section of an extension displayed by `phpinfo()` and when running `php -i`.
+
+use std::{
+    convert::TryInto,
+    ffi::CString,
+    os::raw::{c_char, c_int},
+    ptr,
+};
+
+use crate::{
+    bindings::{
+        display_ini_entries, ext_php_rs_info_print_box, php_info_print_hr,
+        php_info_print_table_colspan_header, php_info_print_table_end, php_info_print_table_header,
+        php_info_print_table_row, php_info_print_table_start, USING_ZTS, ZEND_DEBUG,
+    },
+    errors::{Error, Result},
+};
+
+use super::module::ModuleEntry;
+
+/// The maximum number of columns in a header or row of an [`InfoTable`].
+pub const MAX_COLUMNS: usize = 8;
+
+/// A variadic function printing a header or row of the information table.
+type PrintColumns = unsafe extern "C" fn(num_cols: c_int, ...);
+
+/// Builds the information about an extension displayed by `phpinfo()`.
+///
+/// Headers, rows and colspan headers are grouped into tables, while boxes and separators are
+/// displayed between tables. The INI entries of the extension are displayed after the table.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     info::InfoTable,
+///     module::{ModuleBuilder, ModuleEntry},
+/// };
+///
+/// pub extern "C" fn php_module_info(module: *mut ModuleEntry) {
+///     let module = unsafe { &*module };
+///     let _ = InfoTable::new()
+///         .header(["my extension", "enabled"])
+///         .row(["Version", "1.0.0"])
+///         .build_info()
+///         .separator()
+///         .text_box("Thanks for using my extension!")
+///         .display(module);
+/// }
+///
+/// #[no_mangle]
+/// pub extern "C" fn get_module() -> *mut ModuleEntry {
+///     ModuleBuilder::new("ext-name", "ext-version")
+///         .info_function(php_module_info)
+///         .build()
+///         .unwrap()
+///         .into_raw()
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InfoTable {
+    entries: Vec<Entry>,
+}
+
+/// An entry of the information about an extension.
+#[derive(Debug, Clone, PartialEq)]
+enum Entry {
+    Header(Vec<String>),
+    Row(Vec<String>),
+    Colspan(usize, String),
+    TextBox(String),
+    Separator,
+}
+
+/// An entry which has been converted into C strings, ready to be displayed.
+enum CEntry {
+    Header(Vec<CString>),
+    Row(Vec<CString>),
+    Colspan(c_int, CString),
+    TextBox(CString),
+    Separator,
+}
+
+impl InfoTable {
+    /// Creates a new, empty information table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header to the table.
+    ///
+    /// # Parameters
+    ///
+    /// * `columns` - The columns of the header. There can be at most [`MAX_COLUMNS`] columns.
+    pub fn header<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.entries
+            .push(Entry::Header(columns.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Adds a row to the table.
+    ///
+    /// # Parameters
+    ///
+    /// * `columns` - The columns of the row. There can be at most [`MAX_COLUMNS`] columns.
+    pub fn row<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.entries
+            .push(Entry::Row(columns.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Adds a header spanning multiple columns to the table.
+    ///
+    /// # Parameters
+    ///
+    /// * `columns` - The number of columns the header spans.
+    /// * `header` - The text of the header.
+    pub fn colspan<S: Into<String>>(mut self, columns: usize, header: S) -> Self {
+        self.entries.push(Entry::Colspan(columns, header.into()));
+        self
+    }
+
+    /// Adds a box containing text, displayed between tables.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text inside the box. The text is escaped when displayed as HTML.
+    pub fn text_box<S: Into<String>>(mut self, text: S) -> Self {
+        self.entries.push(Entry::TextBox(text.into()));
+        self
+    }
+
+    /// Adds a horizontal separator, displayed between tables.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(Entry::Separator);
+        self
+    }
+
+    /// Adds rows describing how PHP was built, whether it is a debug build and whether it is
+    /// thread safe.
+    pub fn build_info(self) -> Self {
+        let enabled = |flag| if flag { "enabled" } else { "disabled" };
+
+        self.row(["Debug build", enabled(ZEND_DEBUG != 0)])
+            .row(["Thread safety", enabled(USING_ZTS != 0)])
+    }
+
+    /// Displays the information, followed by the INI entries of the module. Must be called from
+    /// the information function of the module.
+    ///
+    /// Nothing is displayed if the information is invalid.
+    ///
+    /// # Parameters
+    ///
+    /// * `module` - The module the information function was called with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TooManyColumns`] if a header or row contains more than [`MAX_COLUMNS`]
+    /// columns, or [`Error::InvalidCString`] if any of the text contains NUL-bytes.
+    pub fn display(&self, module: &ModuleEntry) -> Result<()> {
+        let entries = self
+            .entries
+            .iter()
+            .map(Entry::to_c_entry)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut in_table = false;
+        for entry in entries {
+            let is_table_entry = !matches!(entry, CEntry::TextBox(_) | CEntry::Separator);
+            if is_table_entry != in_table {
+                unsafe {
+                    if is_table_entry {
+                        php_info_print_table_start();
+                    } else {
+                        php_info_print_table_end();
+                    }
+                }
+                in_table = is_table_entry;
+            }
+
+            match entry {
+                CEntry::Header(columns) => print_columns(php_info_print_table_header, &columns),
+                CEntry::Row(columns) => print_columns(php_info_print_table_row, &columns),
+                CEntry::Colspan(columns, header) => unsafe {
+                    php_info_print_table_colspan_header(columns, header.as_ptr())
+                },
+                CEntry::TextBox(text) => unsafe { ext_php_rs_info_print_box(text.as_ptr()) },
+                CEntry::Separator => unsafe { php_info_print_hr() },
+            }
+        }
+
+        if in_table {
+            unsafe { php_info_print_table_end() };
+        }
+
+        display_ini(module);
+        Ok(())
+    }
+}
+
+impl Entry {
+    /// Converts the entry into C strings, checking the number of columns.
+    fn to_c_entry(&self) -> Result<CEntry> {
+        let columns = |columns: &[String]| {
+            if columns.len() > MAX_COLUMNS {
+                return Err(Error::TooManyColumns(columns.len()));
+            }
+
+            columns
+                .iter()
+                .map(|column| Ok(CString::new(column.as_str())?))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(match self {
+            Self::Header(header) => CEntry::Header(columns(header)?),
+            Self::Row(row) => CEntry::Row(columns(row)?),
+            Self::Colspan(columns, header) => CEntry::Colspan(
+                (*columns).try_into().map_err(|_| Error::IntegerOverflow)?,
+                CString::new(header.as_str())?,
+            ),
+            Self::TextBox(text) => CEntry::TextBox(CString::new(text.as_str())?),
+            Self::Separator => CEntry::Separator,
+        })
+    }
+}
+
+/// Calls a variadic function printing a header or row. Unused arguments are passed as null
+/// pointers and ignored by the function, as it only reads the given number of columns.
+fn print_columns(func: PrintColumns, columns: &[CString]) {
+    let mut ptrs = [ptr::null::<c_char>(); MAX_COLUMNS];
+    for (ptr, column) in ptrs.iter_mut().zip(columns) {
+        *ptr = column.as_ptr();
+    }
+
+    unsafe {
+        func(
+            columns.len() as c_int,
+            ptrs[0],
+            ptrs[1],
+            ptrs[2],
+            ptrs[3],
+            ptrs[4],
+            ptrs[5],
+            ptrs[6],
+            ptrs[7],
+        )
+    };
+}
+
+/// Displays the INI entries registered by a module. Must be called from the information function
+/// of the module. This is done by [`InfoTable::display`].
+///
+/// # Parameters
+///
+/// * `module` - The module the information function was called with.
+pub fn display_ini(module: &ModuleEntry) {
+    unsafe { display_ini_entries(module as *const ModuleEntry as *mut ModuleEntry) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, InfoTable, MAX_COLUMNS};
+    use crate::errors::Error;
+
+    #[test]
+    fn test_invalid_entries() {
+        let table = InfoTable::new().row(vec!["column"; MAX_COLUMNS + 1]);
+        assert_eq!(
+            table.entries[0].to_c_entry().err(),
+            Some(Error::TooManyColumns(MAX_COLUMNS + 1))
+        );
+
+        let table = InfoTable::new().header(["a\0b"]);
+        assert_eq!(
+            table.entries[0].to_c_entry().err(),
+            Some(Error::InvalidCString)
+        );
+
+        let table = InfoTable::new().colspan(2, "header").separator();
+        assert!(table.entries.iter().all(|e| e.to_c_entry().is_ok()));
+        assert_eq!(table.entries[1], Entry::Separator);
+    }
+}
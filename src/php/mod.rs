@@ -13,6 +13,14 @@ pub mod execution_data;
 pub mod flags;
 pub mod function;
 pub mod globals;
+pub mod headers;
+pub mod info;
 pub mod module;
+pub mod observer;
+pub mod output;
 pub mod pack;
+pub mod stream;
+pub mod stream_filter;
+pub mod stream_wrapper;
+pub mod superglobals;
 pub mod types;
@@ -2,24 +2,44 @@
 
 use std::{
     ffi::{c_void, CString},
+    fmt::Debug,
     mem, ptr,
+    sync::Arc,
+};
+
+use parking_lot::{
+    lock_api::{Mutex, RawMutex},
+    RawMutex as RawMutexStruct,
 };
 
 use crate::{
     bindings::{
-        ext_php_rs_php_build_id, zend_module_entry, USING_ZTS, ZEND_DEBUG, ZEND_MODULE_API_NO,
+        ext_php_rs_php_build_id, module_registry, zend_hash_str_find, zend_module_dep,
+        zend_module_entry, MODULE_DEP_CONFLICTS, MODULE_DEP_OPTIONAL, MODULE_DEP_REQUIRED,
+        USING_ZTS, ZEND_DEBUG, ZEND_MODULE_API_NO,
     },
     errors::Result,
 };
 
-use super::function::FunctionEntry;
+use super::{
+    flags::ZendResult,
+    function::FunctionEntry,
+    observer::{self, FcallObserver},
+    stream_filter::{self, StreamFilter},
+    stream_wrapper::{self, StreamWrapper},
+};
 
 /// A Zend module entry. Alias.
 pub type ModuleEntry = zend_module_entry;
+/// A dependency of a Zend module on another module. Alias.
+pub type ModuleDep = zend_module_dep;
 /// A function to be called when the extension is starting up or shutting down.
 pub type StartupShutdownFunc = extern "C" fn(_type: i32, _module_number: i32) -> i32;
 /// A function to be called when `phpinfo();` is called.
 pub type InfoFunc = extern "C" fn(zend_module: *mut ModuleEntry);
+/// A function called with the module number when the module starts up or shuts down, used to
+/// register and unregister the handlers added to the [`ModuleBuilder`].
+pub(crate) type ModuleHook = Arc<dyn Fn(i32) -> Result<()> + Send + Sync>;
 
 /// Builds a Zend extension. Must be called from within an external function called `get_module`,
 /// returning a mutable pointer to a `ModuleEntry`.
@@ -46,12 +66,16 @@ pub type InfoFunc = extern "C" fn(zend_module: *mut ModuleEntry);
 ///         .into_raw()
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ModuleBuilder {
     name: String,
     version: String,
     module: ModuleEntry,
     functions: Vec<FunctionEntry>,
+    dependencies: Vec<(String, DependencyType)>,
+    startup_hooks: Vec<ModuleHook>,
+    shutdown_hooks: Vec<ModuleHook>,
+    error: Option<crate::errors::Error>,
 }
 
 impl ModuleBuilder {
@@ -95,6 +119,10 @@ impl ModuleBuilder {
                 build_id: unsafe { ext_php_rs_php_build_id() },
             },
             functions: vec![],
+            dependencies: vec![],
+            startup_hooks: vec![],
+            shutdown_hooks: vec![],
+            error: None,
         }
     }
 
@@ -158,10 +186,132 @@ impl ModuleBuilder {
         self
     }
 
+    /// Adds a dependency on another extension. PHP starts required and optional dependencies
+    /// before the extension, and refuses to load the extension if a required dependency is
+    /// missing or a conflicting extension is loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the other extension, such as `json`.
+    /// * `ty` - The type of the dependency.
+    pub fn dependency<T: Into<String>>(mut self, name: T, ty: DependencyType) -> Self {
+        self.dependencies.push((name.into(), ty));
+        self
+    }
+
+    /// Registers an observer of function calls when the extension starts up. See the
+    /// [`observer`](crate::php::observer) module for an example.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer to register.
+    pub fn fcall_observer<T: FcallObserver>(mut self, observer: T) -> Self {
+        let (startup, shutdown) = observer::hooks(observer);
+        self.startup_hooks.push(startup);
+        self.shutdown_hooks.push(shutdown);
+        self
+    }
+
+    /// Registers a stream wrapper for the given protocol when the extension starts up, allowing
+    /// PHP code to open URLs such as `protocol://path` through the wrapper. See the
+    /// [`stream_wrapper`](crate::php::stream_wrapper) module for an example.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - The protocol handled by the wrapper, without the `://` suffix.
+    /// * `wrapper` - The wrapper handling the protocol.
+    ///
+    /// An invalid protocol, such as one containing a NUL byte, is reported by [`build`].
+    ///
+    /// [`build`]: ModuleBuilder::build
+    pub fn stream_wrapper<T: StreamWrapper>(mut self, protocol: &str, wrapper: T) -> Self {
+        match stream_wrapper::hooks(protocol, wrapper) {
+            Ok((startup, shutdown)) => {
+                self.startup_hooks.push(startup);
+                self.shutdown_hooks.push(shutdown);
+            }
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
+    /// Registers a stream filter with the given name when the extension starts up, allowing PHP
+    /// code to add the filter to streams with `stream_filter_append()`. See the
+    /// [`stream_filter`](crate::php::stream_filter) module for an example.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the filter. A name ending in `.*` registers the filter for all
+    ///   names starting with the given prefix.
+    ///
+    /// An invalid name, such as one containing a NUL byte, is reported by [`build`].
+    ///
+    /// [`build`]: ModuleBuilder::build
+    pub fn stream_filter<T: StreamFilter>(mut self, name: &str) -> Self {
+        match stream_filter::hooks::<T>(name) {
+            Ok((startup, shutdown)) => {
+                self.startup_hooks.push(startup);
+                self.shutdown_hooks.push(shutdown);
+            }
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
     /// Builds the extension and returns a `ModuleEntry`.
     ///
     /// Returns a result containing the module entry if successful.
     pub fn build(mut self) -> Result<ModuleEntry> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        #[cfg(any(docs, feature = "alloc"))]
+        super::alloc::mark_php_thread();
+
+        // Requests are tracked so that memory from the Zend memory manager is only freed during
+        // the request it was allocated in.
+        #[cfg(any(docs, feature = "alloc"))]
+        {
+            let mut hooks = MODULE_HOOKS.lock();
+            hooks.request_startup_func = self.module.request_startup_func.replace(request_startup);
+            hooks.post_deactivate_func = self.module.post_deactivate_func.replace(post_deactivate);
+        }
+
+        if !self.startup_hooks.is_empty() || !self.shutdown_hooks.is_empty() {
+            let mut hooks = MODULE_HOOKS.lock();
+            hooks.startup_func = self.module.module_startup_func.replace(module_startup);
+            hooks.shutdown_func = self.module.module_shutdown_func.replace(module_shutdown);
+            hooks.startup = self.startup_hooks;
+            hooks.shutdown = self.shutdown_hooks;
+        }
+
+        if !self.dependencies.is_empty() {
+            let mut deps = self
+                .dependencies
+                .into_iter()
+                .map(|(name, ty)| {
+                    Ok(ModuleDep {
+                        name: CString::new(name)?.into_raw(),
+                        rel: ptr::null(),
+                        version: ptr::null(),
+                        type_: ty as u8,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            deps.push(ModuleDep {
+                name: ptr::null(),
+                rel: ptr::null(),
+                version: ptr::null(),
+                type_: 0,
+            });
+            self.module.deps = Box::into_raw(deps.into_boxed_slice()) as *const ModuleDep;
+        }
+
         self.functions.push(FunctionEntry::end());
         self.module.functions =
             Box::into_raw(self.functions.into_boxed_slice()) as *const FunctionEntry;
@@ -172,11 +322,171 @@ impl ModuleBuilder {
     }
 }
 
+impl Debug for ModuleBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModuleBuilder")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("module", &self.module)
+            .field("functions", &self.functions)
+            .field("dependencies", &self.dependencies)
+            .field("startup_hooks", &self.startup_hooks.len())
+            .field("shutdown_hooks", &self.shutdown_hooks.len())
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// The type of a dependency of a module on another module.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyType {
+    /// The other module must be loaded, and is started before the module.
+    Required = MODULE_DEP_REQUIRED as u8,
+    /// The other module is started before the module if it is loaded.
+    Optional = MODULE_DEP_OPTIONAL as u8,
+    /// The module cannot be loaded alongside the other module.
+    Conflicts = MODULE_DEP_CONFLICTS as u8,
+}
+
+/// The hooks of the module, along with the startup and shutdown functions they wrap.
+struct ModuleHooks {
+    startup_func: Option<unsafe extern "C" fn(i32, i32) -> i32>,
+    shutdown_func: Option<unsafe extern "C" fn(i32, i32) -> i32>,
+    #[cfg(any(docs, feature = "alloc"))]
+    request_startup_func: Option<unsafe extern "C" fn(i32, i32) -> i32>,
+    #[cfg(any(docs, feature = "alloc"))]
+    post_deactivate_func: Option<unsafe extern "C" fn() -> i32>,
+    startup: Vec<ModuleHook>,
+    shutdown: Vec<ModuleHook>,
+}
+
+static MODULE_HOOKS: Mutex<RawMutexStruct, ModuleHooks> = Mutex::const_new(
+    RawMutex::INIT,
+    ModuleHooks {
+        startup_func: None,
+        shutdown_func: None,
+        #[cfg(any(docs, feature = "alloc"))]
+        request_startup_func: None,
+        #[cfg(any(docs, feature = "alloc"))]
+        post_deactivate_func: None,
+        startup: Vec::new(),
+        shutdown: Vec::new(),
+    },
+);
+
+/// Startup function of modules with hooks. Runs the startup hooks before calling the startup
+/// function of the module.
+extern "C" fn module_startup(ty: i32, module_number: i32) -> i32 {
+    // The lock is released before running the hooks, which may call back into the module.
+    let (hooks, startup_func) = {
+        let hooks = MODULE_HOOKS.lock();
+        (hooks.startup.clone(), hooks.startup_func)
+    };
+    if !run_hooks(&hooks, module_number) {
+        return ZendResult::Failure.bits();
+    }
+
+    match startup_func {
+        Some(func) => unsafe { func(ty, module_number) },
+        None => ZendResult::Success.bits(),
+    }
+}
+
+/// Shutdown function of modules with hooks. Calls the shutdown function of the module before
+/// running the shutdown hooks.
+extern "C" fn module_shutdown(ty: i32, module_number: i32) -> i32 {
+    let (hooks, shutdown_func) = {
+        let hooks = MODULE_HOOKS.lock();
+        (hooks.shutdown.clone(), hooks.shutdown_func)
+    };
+    let result = match shutdown_func {
+        Some(func) => unsafe { func(ty, module_number) },
+        None => ZendResult::Success.bits(),
+    };
+
+    if !run_hooks(&hooks, module_number) {
+        return ZendResult::Failure.bits();
+    }
+
+    result
+}
+
+/// Runs all of the given hooks, even if one of them fails, so that every handler is registered
+/// or unregistered. Returns whether all of the hooks succeeded.
+fn run_hooks(hooks: &[ModuleHook], module_number: i32) -> bool {
+    let mut ok = true;
+    for hook in hooks {
+        if hook(module_number).is_err() {
+            ok = false;
+        }
+    }
+    ok
+}
+
+/// Request startup function of modules using the allocator. Records that a request has started
+/// before calling the request startup function of the module.
+#[cfg(any(docs, feature = "alloc"))]
+extern "C" fn request_startup(ty: i32, module_number: i32) -> i32 {
+    super::alloc::request_started();
+
+    // The lock is released before calling the function, which may call back into the module.
+    let func = MODULE_HOOKS.lock().request_startup_func;
+    match func {
+        Some(func) => unsafe { func(ty, module_number) },
+        None => ZendResult::Success.bits(),
+    }
+}
+
+/// Post-deactivate function of modules using the allocator, called after the request has been
+/// deactivated and before PHP releases the memory of the request. Calls the post-deactivate
+/// function of the module before recording that the request has ended.
+#[cfg(any(docs, feature = "alloc"))]
+extern "C" fn post_deactivate() -> i32 {
+    let func = MODULE_HOOKS.lock().post_deactivate_func;
+    let result = match func {
+        Some(func) => unsafe { func() },
+        None => ZendResult::Success.bits(),
+    };
+
+    super::alloc::request_ended();
+    result
+}
+
 impl ModuleEntry {
     /// Converts the module entry into a raw pointer, releasing it to the C world.
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
+
+    /// Attempts to find a loaded module by name. Module names are case-insensitive.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the module, such as `json`.
+    pub fn find(name: &str) -> Option<&'static Self> {
+        let name = name.to_ascii_lowercase();
+        let zv = unsafe {
+            zend_hash_str_find(
+                ptr::addr_of!(module_registry),
+                name.as_ptr() as *const _,
+                name.len() as _,
+            )
+            .as_ref()
+        }?;
+
+        unsafe { (zv.value.ptr as *const Self).as_ref() }
+    }
+
+    /// Returns whether a module with the given name is loaded. Module names are
+    /// case-insensitive.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the module, such as `json`.
+    pub fn is_loaded(name: &str) -> bool {
+        Self::find(name).is_some()
+    }
 }
 
 /// Called by startup functions registered with the `#[php_startup]` macro. Initializes all
@@ -0,0 +1,236 @@
+//! Observers of function calls, using the observer API of the Zend engine to run handlers when
+//! functions begin and end executing. Useful for tracing and profiling extensions.
+//!
+//! Observers must be registered when the extension starts up, with
+//! [`ModuleBuilder::fcall_observer`]. PHP 8.0 only observes calls to functions written in PHP,
+//! not internal functions.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use ext_php_rs::php::{
+//!     execution_data::ExecutionData,
+//!     module::ModuleBuilder,
+//!     observer::FcallObserver,
+//!     types::zval::Zval,
+//! };
+//!
+//! /// Counts the calls to functions in the `App` namespace.
+//! #[derive(Default)]
+//! struct CallCounter {
+//!     calls: AtomicU64,
+//! }
+//!
+//! impl FcallObserver for CallCounter {
+//!     fn should_observe(&self, ex: &ExecutionData) -> bool {
+//!         ex.function_name()
+//!             .map(|name| name.starts_with("App\\"))
+//!             .unwrap_or(false)
+//!     }
+//!
+//!     fn begin(&self, ex: &ExecutionData) {
+//!         self.calls.fetch_add(1, Ordering::Relaxed);
+//!         eprintln!(
+//!             "{} called at {}:{} with {} arguments",
+//!             ex.function_name().unwrap_or("{main}"),
+//!             ex.file().unwrap_or("unknown"),
+//!             ex.line().unwrap_or(0),
+//!             ex.args().len()
+//!         );
+//!     }
+//!
+//!     fn end(&self, ex: &ExecutionData, retval: &Zval) {
+//!         eprintln!("{} returned {:?}", ex.function_name().unwrap_or("{main}"), retval);
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn get_module() -> *mut ext_php_rs::php::module::ModuleEntry {
+//!     ModuleBuilder::new("counter", "0.1.0")
+//!         .fcall_observer(CallCounter::default())
+//!         .build()
+//!         .unwrap()
+//!         .into_raw()
+//! }
+//! ```
+//!
+//! [`ModuleBuilder::fcall_observer`]: crate::php::module::ModuleBuilder::fcall_observer
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::{
+    lock_api::{RawRwLock, RwLock},
+    RawRwLock as RawRwLockStruct,
+};
+
+use crate::bindings::{
+    zend_execute_data, zend_observer_fcall_handlers, zend_observer_fcall_register, zval,
+};
+
+use super::{execution_data::ExecutionData, module::ModuleHook, types::zval::Zval};
+
+/// Observes calls to functions, running handlers when a function begins and ends executing.
+///
+/// The handlers are called on every observed function call, and should be kept cheap. As the
+/// observer is shared between threads, any state it holds must use interior mutability. When
+/// multiple observers are registered, each of them is only called for the functions it observes.
+pub trait FcallObserver: Send + Sync + 'static {
+    /// Returns whether calls to a function should be observed. Called once for each function,
+    /// when it is first called, and the result is cached by PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `ex` - The execution data of the first call to the function.
+    fn should_observe(&self, _ex: &ExecutionData) -> bool {
+        true
+    }
+
+    /// Called when an observed function begins executing, after its arguments have been
+    /// received.
+    ///
+    /// # Parameters
+    ///
+    /// * `ex` - The execution data of the call.
+    fn begin(&self, _ex: &ExecutionData) {}
+
+    /// Called when an observed function ends executing, including when it ends by throwing an
+    /// exception.
+    ///
+    /// # Parameters
+    ///
+    /// * `ex` - The execution data of the call.
+    /// * `retval` - The value returned by the function, which is `null` if the function did not
+    ///   return a value.
+    fn end(&self, _ex: &ExecutionData, _retval: &Zval) {}
+}
+
+/// The observers registered by the extension.
+static OBSERVERS: RwLock<RawRwLockStruct, Vec<Arc<dyn FcallObserver>>> =
+    RwLock::const_new(RawRwLockStruct::INIT, Vec::new());
+
+/// The observers of each observed function, keyed by the address of the function.
+static OBSERVED: RwLock<RawRwLockStruct, BTreeMap<usize, Observers>> =
+    RwLock::const_new(RawRwLockStruct::INIT, BTreeMap::new());
+
+/// The observers of a function.
+type Observers = Arc<[Arc<dyn FcallObserver>]>;
+
+/// Whether the handlers have been registered with the Zend engine.
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the hooks registering the observer when the extension starts up, and removing it when
+/// the extension shuts down.
+///
+/// # Parameters
+///
+/// * `observer` - The observer to register.
+pub(crate) fn hooks<T: FcallObserver>(observer: T) -> (ModuleHook, ModuleHook) {
+    let observer: Arc<dyn FcallObserver> = Arc::new(observer);
+
+    let startup: ModuleHook = Arc::new(move |_| {
+        OBSERVERS.write().push(observer.clone());
+        if !REGISTERED.swap(true, Ordering::SeqCst) {
+            unsafe { zend_observer_fcall_register(Some(observer_init)) };
+        }
+        Ok(())
+    });
+    let shutdown: ModuleHook = Arc::new(|_| {
+        OBSERVERS.write().clear();
+        OBSERVED.write().clear();
+        REGISTERED.store(false, Ordering::SeqCst);
+        Ok(())
+    });
+
+    (startup, shutdown)
+}
+
+/// Called by the Zend engine when a function is first called, returning the handlers to run on
+/// calls to the function.
+unsafe extern "C" fn observer_init(
+    execute_data: *mut zend_execute_data,
+) -> zend_observer_fcall_handlers {
+    let observe = match execute_data.as_ref() {
+        Some(ex) => {
+            let observers = matching_observers(ex);
+            let observe = !observers.is_empty();
+
+            // The address of a function may be reused by a function defined later, so previous
+            // entries are replaced.
+            let mut observed = OBSERVED.write();
+            if observe {
+                observed.insert(ex.func as usize, observers);
+            } else {
+                observed.remove(&(ex.func as usize));
+            }
+            observe
+        }
+        None => false,
+    };
+
+    if observe {
+        zend_observer_fcall_handlers {
+            begin: Some(observer_begin),
+            end: Some(observer_end),
+        }
+    } else {
+        zend_observer_fcall_handlers {
+            begin: None,
+            end: None,
+        }
+    }
+}
+
+/// Called by the Zend engine when an observed function begins executing.
+unsafe extern "C" fn observer_begin(execute_data: *mut zend_execute_data) {
+    if let Some(ex) = execute_data.as_ref() {
+        for obs in observers_of(ex).iter() {
+            obs.begin(ex);
+        }
+    }
+}
+
+/// Called by the Zend engine when an observed function ends executing.
+unsafe extern "C" fn observer_end(execute_data: *mut zend_execute_data, retval: *mut zval) {
+    let ex = match execute_data.as_ref() {
+        Some(ex) => ex,
+        None => return,
+    };
+
+    let null = Zval::new();
+    let retval = retval.as_ref().unwrap_or(&null);
+    for obs in observers_of(ex).iter() {
+        obs.end(ex, retval);
+    }
+}
+
+/// Returns the observers which observe calls to the function being executed.
+fn matching_observers(ex: &ExecutionData) -> Observers {
+    OBSERVERS
+        .read()
+        .iter()
+        .filter(|obs| obs.should_observe(ex))
+        .cloned()
+        .collect()
+}
+
+/// Returns the observers of the function being executed, as recorded when the function was first
+/// called. The observers are returned rather than borrowed, so that no lock is held while they run.
+fn observers_of(ex: &ExecutionData) -> Observers {
+    let key = ex.func as usize;
+    if let Some(observers) = OBSERVED.read().get(&key) {
+        return observers.clone();
+    }
+
+    // Functions may share the handlers of the function they were copied from, such as closures,
+    // in which case their observers are found the same way.
+    let observers = matching_observers(ex);
+    OBSERVED.write().insert(key, observers.clone());
+    observers
+}
@@ -0,0 +1,290 @@
+//! Functions for writing to the PHP output stream and controlling output buffering, the
+//! equivalent of `echo` and the `ob_*()` family of functions in PHP.
+//!
+//! Output written from Rust passes through the active output buffers, the same as output written
+//! from PHP, so it is captured by `ob_start()` and processed by output handlers.
+
+use std::{
+    ffi::c_void,
+    fmt,
+    io::{self, Write},
+    os::raw::{c_char, c_int},
+    slice,
+};
+
+use crate::{
+    bindings::{
+        php_output_clean, php_output_context, php_output_discard, php_output_end, php_output_flush,
+        php_output_get_contents, php_output_get_level, php_output_handler_create_internal,
+        php_output_handler_free, php_output_handler_set_context, php_output_handler_start,
+        php_output_start_default, php_output_write, php_output_write_unbuffered,
+    },
+    errors::{Error, Result},
+    php::{
+        flags::{OutputHandlerFlags, OutputOp, ZendResult},
+        types::zval::Zval,
+    },
+};
+
+/// Writes bytes to the PHP output stream, passing through the active output buffers. Returns the
+/// number of bytes written.
+///
+/// # Parameters
+///
+/// * `buf` - The bytes to write.
+pub fn write(buf: &[u8]) -> usize {
+    unsafe { php_output_write(buf.as_ptr() as *const c_char, buf.len() as _) as usize }
+}
+
+/// Writes bytes directly to the SAPI, bypassing the active output buffers. Returns the number of
+/// bytes written.
+///
+/// # Parameters
+///
+/// * `buf` - The bytes to write.
+pub fn write_unbuffered(buf: &[u8]) -> usize {
+    unsafe { php_output_write_unbuffered(buf.as_ptr() as *const c_char, buf.len() as _) as usize }
+}
+
+/// INTERNAL: Writes formatted arguments to the output stream. Used by the [`crate::php_print`]
+/// and [`crate::php_println`] macros.
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    let _ = PhpOutput.write_fmt(args);
+}
+
+/// A handle to the PHP output stream, implementing [`Write`].
+///
+/// Writes pass through the active output buffers, the same as `echo` in PHP. Flushing the handle
+/// does nothing, as the output is handed to PHP as soon as it is written. Use [`flush`] to flush
+/// the active output buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::Write;
+/// use ext_php_rs::php::output::PhpOutput;
+///
+/// writeln!(PhpOutput, "Hello, {}!", "world").unwrap();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PhpOutput;
+
+impl Write for PhpOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Converts the result of an output function into a [`Result`].
+fn result(result: c_int) -> Result<()> {
+    if result < 0 {
+        Err(Error::OutputBuffer)
+    } else {
+        Ok(())
+    }
+}
+
+/// Starts a new output buffer, the same as `ob_start()` in PHP.
+pub fn start() -> Result<()> {
+    result(unsafe { php_output_start_default() })
+}
+
+/// Returns the nesting level of the output buffers, the same as `ob_get_level()` in PHP. Returns
+/// zero if there are no active output buffers.
+pub fn level() -> i32 {
+    unsafe { php_output_get_level() }
+}
+
+/// Returns the contents of the active output buffer, the same as `ob_get_contents()` in PHP.
+/// Returns [`None`] if there is no active output buffer.
+pub fn contents() -> Option<Vec<u8>> {
+    let mut zv = Zval::new();
+    result(unsafe { php_output_get_contents(&mut zv) }).ok()?;
+    zv.zend_str().map(|str| str.as_bytes().to_vec())
+}
+
+/// Flushes the contents of the active output buffer to the buffer below it, or to the SAPI if
+/// there is no buffer below it, the same as `ob_flush()` in PHP.
+pub fn flush() -> Result<()> {
+    result(unsafe { php_output_flush() })
+}
+
+/// Discards the contents of the active output buffer, the same as `ob_clean()` in PHP.
+pub fn clean() -> Result<()> {
+    result(unsafe { php_output_clean() })
+}
+
+/// Flushes the contents of the active output buffer and removes the buffer, the same as
+/// `ob_end_flush()` in PHP.
+pub fn end() -> Result<()> {
+    result(unsafe { php_output_end() })
+}
+
+/// Discards the contents of the active output buffer and removes the buffer, the same as
+/// `ob_end_clean()` in PHP.
+pub fn discard() -> Result<()> {
+    result(unsafe { php_output_discard() })
+}
+
+/// Returns the contents of the active output buffer and removes the buffer, the same as
+/// `ob_get_clean()` in PHP. Returns [`None`] if there is no active output buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{php::output, php_print};
+///
+/// output::start().unwrap();
+/// php_print!("captured");
+/// assert_eq!(output::get_clean().unwrap(), b"captured");
+/// ```
+pub fn get_clean() -> Option<Vec<u8>> {
+    let contents = contents()?;
+    discard().ok()?;
+    Some(contents)
+}
+
+/// Processes the output written while the handler is active, such as to compress the output or
+/// to fill in a template.
+///
+/// Handlers are implemented for closures taking the same parameters as
+/// [`OutputHandler::handle`].
+pub trait OutputHandler: 'static {
+    /// Processes a chunk of output, returning the output to pass on to the next output buffer.
+    /// Returns [`None`] if the output could not be processed, in which case the handler is
+    /// disabled and the output is passed on unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `input` - The output written since the handler was last called.
+    /// * `op` - The operations being performed on the output buffer, such as whether this is the
+    ///   first or final call to the handler.
+    fn handle(&mut self, input: &[u8], op: OutputOp) -> Option<Vec<u8>>;
+}
+
+impl<F> OutputHandler for F
+where
+    F: FnMut(&[u8], OutputOp) -> Option<Vec<u8>> + 'static,
+{
+    fn handle(&mut self, input: &[u8], op: OutputOp) -> Option<Vec<u8>> {
+        self(input, op)
+    }
+}
+
+/// The context of an output handler, holding the processed output until PHP has passed it on.
+struct HandlerContext<H> {
+    handler: H,
+    buffer: Vec<u8>,
+}
+
+/// Starts a new output buffer which passes its contents through the given handler, the same as
+/// calling `ob_start()` in PHP with a callback.
+///
+/// # Parameters
+///
+/// * `name` - The name of the handler, as returned from `ob_list_handlers()`.
+/// * `handler` - The handler to process the output.
+/// * `chunk_size` - The size of the buffer after which the handler is called. If zero, the
+///   handler is only called when the buffer is flushed, cleaned or removed.
+/// * `flags` - The operations PHP code is allowed to perform on the buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::{flags::OutputHandlerFlags, output};
+///
+/// output::start_handler(
+///     "uppercase",
+///     |input: &[u8], _| Some(input.to_ascii_uppercase()),
+///     0,
+///     OutputHandlerFlags::Standard,
+/// )
+/// .unwrap();
+/// ```
+pub fn start_handler<H: OutputHandler>(
+    name: &str,
+    handler: H,
+    chunk_size: usize,
+    flags: OutputHandlerFlags,
+) -> Result<()> {
+    let mut ptr = unsafe {
+        php_output_handler_create_internal(
+            name.as_ptr() as *const c_char,
+            name.len() as _,
+            Some(handler_func::<H>),
+            chunk_size as _,
+            flags.bits() as _,
+        )
+    };
+
+    if ptr.is_null() {
+        return Err(Error::OutputBuffer);
+    }
+
+    let ctx = Box::new(HandlerContext {
+        handler,
+        buffer: Vec::new(),
+    });
+
+    unsafe {
+        php_output_handler_set_context(
+            ptr,
+            Box::into_raw(ctx) as *mut c_void,
+            Some(handler_dtor::<H>),
+        );
+
+        if php_output_handler_start(ptr) < 0 {
+            php_output_handler_free(&mut ptr);
+            return Err(Error::OutputBuffer);
+        }
+    }
+
+    Ok(())
+}
+
+/// Called by PHP to process the contents of the output buffer.
+unsafe extern "C" fn handler_func<H: OutputHandler>(
+    handler_context: *mut *mut c_void,
+    output_context: *mut php_output_context,
+) -> c_int {
+    let ctx = match (*handler_context as *mut HandlerContext<H>).as_mut() {
+        Some(ctx) => ctx,
+        None => return ZendResult::Failure.bits(),
+    };
+    let output = match output_context.as_mut() {
+        Some(output) => output,
+        None => return ZendResult::Failure.bits(),
+    };
+
+    let input = if output.in_.data.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(output.in_.data as *const u8, output.in_.used as _)
+    };
+
+    match ctx
+        .handler
+        .handle(input, OutputOp::from_bits_truncate(output.op as _))
+    {
+        Some(buffer) => {
+            // PHP copies the output before the handler is called again or destroyed, so the
+            // buffer does not need to be allocated by PHP.
+            ctx.buffer = buffer;
+            output.out.data = ctx.buffer.as_mut_ptr() as *mut c_char;
+            output.out.size = ctx.buffer.capacity() as _;
+            output.out.used = ctx.buffer.len() as _;
+            ZendResult::Success.bits()
+        }
+        None => ZendResult::Failure.bits(),
+    }
+}
+
+/// Called by PHP to release the context of the handler.
+unsafe extern "C" fn handler_dtor<H: OutputHandler>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr as *mut HandlerContext<H>));
+}
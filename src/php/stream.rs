@@ -0,0 +1,489 @@
+//! Reading and writing PHP streams from Rust.
+//!
+//! Streams opened through PHP support all of the registered stream wrappers, such as `phar://`,
+//! `php://memory` and `compress.zlib://`, as well as wrappers registered by PHP code.
+
+use std::{
+    convert::TryInto,
+    ffi::CString,
+    fmt::Debug,
+    io::{self, BufRead, Read, Seek, SeekFrom, Write},
+    mem::ManuallyDrop,
+    os::raw::c_char,
+    ptr::{self, NonNull},
+};
+
+use crate::{
+    bindings::{
+        _php_stream_flush, _php_stream_free, _php_stream_read, _php_stream_seek, _php_stream_tell,
+        _php_stream_write, ext_php_rs_php_stream_open_wrapper, ext_php_rs_php_stream_to_zval,
+        php_file_le_pstream, php_file_le_stream, php_le_stream_context, php_stream,
+        php_stream_context, php_stream_context_alloc, php_stream_context_get_option,
+        php_stream_context_set_option, zend_list_delete, zend_resource, PHP_STREAM_FREE_CLOSE,
+        SEEK_CUR, SEEK_END, SEEK_SET,
+    },
+    errors::{Error, Result},
+    php::{
+        enums::DataType,
+        flags::StreamOpenFlags,
+        types::{
+            rc::PhpRc,
+            type_spec::TypeSpec,
+            zval::{FromZval, IntoZval, Zval},
+        },
+    },
+};
+
+/// The size of the read buffer of a stream.
+const BUF_SIZE: usize = 8192;
+
+/// Creates an I/O error for a failed stream operation.
+pub(crate) fn error(message: &'static str) -> io::Error {
+    io::Error::other(message)
+}
+
+/// A PHP stream, which can be read and written through the [`std::io`] traits.
+///
+/// Streams are either opened from Rust with [`Stream::open`], or received from PHP as a resource
+/// through [`FromZval`]. The stream holds a reference to its resource, and the stream is closed
+/// once all references to it have been released, so streams opened from Rust are closed when
+/// dropped, while streams received from PHP stay open for the PHP code which passed them.
+///
+/// Reads are buffered to implement [`BufRead`]. The buffer is discarded when seeking or writing,
+/// moving the position of the stream back to the last byte returned from the stream.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::{BufRead, BufReader, Write};
+/// use ext_php_rs::php::stream::Stream;
+///
+/// let mut stream = Stream::open("php://memory", "w+").unwrap();
+/// stream.write_all(b"first\nsecond\n").unwrap();
+///
+/// let stream = Stream::open("compress.zlib:///tmp/lines.gz", "r").unwrap();
+/// for line in stream.lines() {
+///     println!("{}", line.unwrap());
+/// }
+/// ```
+pub struct Stream {
+    ptr: NonNull<php_stream>,
+    buf: Vec<u8>,
+    pos: usize,
+    filled: usize,
+}
+
+impl Stream {
+    /// Opens a stream through the stream wrapper handling the given path, emitting a warning if
+    /// the stream could not be opened.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path or URL to open.
+    /// * `mode` - The mode to open the stream in, the same as `fopen()`, such as `"rb"`.
+    pub fn open(path: &str, mode: &str) -> Result<Self> {
+        Self::open_with(path, mode, StreamOpenFlags::ReportErrors, None)
+    }
+
+    /// Opens a stream through the stream wrapper handling the given path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path or URL to open.
+    /// * `mode` - The mode to open the stream in, the same as `fopen()`, such as `"rb"`.
+    /// * `flags` - Flags controlling how the stream is opened.
+    /// * `context` - The context passed to the stream wrapper, such as HTTP headers for the
+    ///   `http://` wrapper. The default context is used if [`None`].
+    pub fn open_with(
+        path: &str,
+        mode: &str,
+        flags: StreamOpenFlags,
+        context: Option<&StreamContext>,
+    ) -> Result<Self> {
+        let c_path = CString::new(path)?;
+        let c_mode = CString::new(mode)?;
+
+        let ptr = unsafe {
+            ext_php_rs_php_stream_open_wrapper(
+                c_path.as_ptr(),
+                c_mode.as_ptr(),
+                flags.bits() as _,
+                context
+                    .map(|ctx| ctx.ptr.as_ptr())
+                    .unwrap_or(ptr::null_mut()),
+            )
+        };
+
+        NonNull::new(ptr)
+            .map(Self::from_ptr)
+            .ok_or_else(|| Error::StreamOpen(path.to_string()))
+    }
+
+    /// Creates a stream from a pointer to a PHP stream, taking ownership of the reference held
+    /// by the caller.
+    fn from_ptr(ptr: NonNull<php_stream>) -> Self {
+        Self {
+            ptr,
+            buf: Vec::new(),
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns a pointer to the underlying PHP stream.
+    pub fn as_ptr(&self) -> *mut php_stream {
+        self.ptr.as_ptr()
+    }
+
+    /// Closes the stream, even if it is still referenced by PHP code.
+    pub fn close(mut self) -> io::Result<()> {
+        self.discard_buffer();
+        let result = unsafe { _php_stream_free(self.ptr.as_ptr(), PHP_STREAM_FREE_CLOSE as _) };
+        // The reference held by the stream is released when the stream is freed.
+        std::mem::forget(self);
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(error("Failed to close stream."))
+        }
+    }
+
+    /// Returns the resource of the stream.
+    fn resource(&self) -> *mut zend_resource {
+        unsafe { self.ptr.as_ref() }.res
+    }
+
+    /// Reads from the stream, bypassing the read buffer.
+    fn read_unbuffered(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = unsafe {
+            _php_stream_read(
+                self.ptr.as_ptr(),
+                buf.as_mut_ptr() as *mut c_char,
+                buf.len() as _,
+            )
+        };
+
+        read.try_into()
+            .map_err(|_| error("Failed to read from stream."))
+    }
+
+    /// Discards the read buffer, moving the position of the stream back to the last byte
+    /// returned from the stream. The buffer is kept if the stream is not seekable, such as a
+    /// socket, as reads and writes are then independent.
+    fn discard_buffer(&mut self) -> bool {
+        let remaining = self.filled - self.pos;
+        if remaining > 0
+            && unsafe { _php_stream_seek(self.ptr.as_ptr(), -(remaining as i64), SEEK_CUR as _) }
+                != 0
+        {
+            return false;
+        }
+
+        self.pos = 0;
+        self.filled = 0;
+        true
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Avoid copying large reads through the buffer when it is empty.
+        if self.pos == self.filled && buf.len() >= BUF_SIZE {
+            return self.read_unbuffered(buf);
+        }
+
+        let read = {
+            let available = self.fill_buf()?;
+            let read = available.len().min(buf.len());
+            buf[..read].copy_from_slice(&available[..read]);
+            read
+        };
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl BufRead for Stream {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.filled {
+            let mut buf = std::mem::take(&mut self.buf);
+            buf.resize(BUF_SIZE, 0);
+            let read = self.read_unbuffered(&mut buf);
+            self.buf = buf;
+
+            self.pos = 0;
+            self.filled = read?;
+        }
+
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.discard_buffer();
+
+        let written = unsafe {
+            _php_stream_write(
+                self.ptr.as_ptr(),
+                buf.as_ptr() as *const c_char,
+                buf.len() as _,
+            )
+        };
+
+        written
+            .try_into()
+            .map_err(|_| error("Failed to write to stream."))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if unsafe { _php_stream_flush(self.ptr.as_ptr(), 0) } == 0 {
+            Ok(())
+        } else {
+            Err(error("Failed to flush stream."))
+        }
+    }
+}
+
+impl Seek for Stream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (
+                offset
+                    .try_into()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Invalid offset."))?,
+                SEEK_SET,
+            ),
+            SeekFrom::End(offset) => (offset, SEEK_END),
+            SeekFrom::Current(offset) => {
+                // The position of the stream is ahead of the caller by the unread part of the
+                // buffer.
+                (offset - (self.filled - self.pos) as i64, SEEK_CUR)
+            }
+        };
+
+        self.pos = 0;
+        self.filled = 0;
+
+        if unsafe { _php_stream_seek(self.ptr.as_ptr(), offset, whence as _) } != 0 {
+            return Err(error("Failed to seek stream."));
+        }
+
+        unsafe { _php_stream_tell(self.ptr.as_ptr()) }
+            .try_into()
+            .map_err(|_| error("Failed to seek stream."))
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        // Give back the buffered bytes, as other references to the stream may still read from it.
+        self.discard_buffer();
+        unsafe { zend_list_delete(self.resource()) };
+    }
+}
+
+impl Debug for Stream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stream")
+            .field("ptr", &self.ptr)
+            .field("buffered", &(self.filled - self.pos))
+            .finish()
+    }
+}
+
+impl FromZval<'_> for Stream {
+    const TYPE: DataType = DataType::Resource;
+
+    // PHP has no type declaration for resources.
+    fn type_spec() -> TypeSpec {
+        DataType::Mixed.into()
+    }
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let res = unsafe { zval.resource()?.as_mut()? };
+        let (le_stream, le_pstream) = unsafe { (php_file_le_stream(), php_file_le_pstream()) };
+        if res.type_ != le_stream && res.type_ != le_pstream {
+            return None;
+        }
+
+        let ptr = NonNull::new(res.ptr as *mut php_stream)?;
+        res.inc_count();
+        Some(Self::from_ptr(ptr))
+    }
+}
+
+impl IntoZval for Stream {
+    const TYPE: DataType = DataType::Resource;
+
+    // PHP has no type declaration for resources.
+    fn type_spec() -> TypeSpec {
+        DataType::Mixed.into()
+    }
+
+    fn set_zval(mut self, zv: &mut Zval, _: bool) -> Result<()> {
+        self.discard_buffer();
+        // The reference held by the stream is moved into the zval.
+        unsafe { ext_php_rs_php_stream_to_zval(self.ptr.as_ptr(), zv) };
+        std::mem::forget(self);
+        Ok(())
+    }
+}
+
+/// A stream context, holding the options passed to stream wrappers when opening streams, such as
+/// the HTTP method used by the `http://` wrapper.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::Read;
+/// use ext_php_rs::php::{flags::StreamOpenFlags, stream::{Stream, StreamContext}};
+///
+/// let mut context = StreamContext::new();
+/// context.set_option("http", "method", "POST").unwrap();
+/// context.set_option("http", "content", "name=value").unwrap();
+///
+/// let mut stream = Stream::open_with(
+///     "https://example.com/",
+///     "r",
+///     StreamOpenFlags::ReportErrors,
+///     Some(&context),
+/// )
+/// .unwrap();
+///
+/// let mut response = String::new();
+/// stream.read_to_string(&mut response).unwrap();
+/// ```
+pub struct StreamContext {
+    ptr: NonNull<php_stream_context>,
+}
+
+impl StreamContext {
+    /// Creates a new stream context without any options.
+    pub fn new() -> Self {
+        Self {
+            ptr: NonNull::new(unsafe { php_stream_context_alloc() })
+                .expect("Failed to allocate stream context."),
+        }
+    }
+
+    /// Returns a pointer to the underlying PHP stream context.
+    pub fn as_ptr(&self) -> *mut php_stream_context {
+        self.ptr.as_ptr()
+    }
+
+    /// Creates a context from a pointer without taking a reference to the context, which must
+    /// outlive the returned context.
+    pub(crate) fn from_ptr_borrowed(ptr: *mut php_stream_context) -> Option<ManuallyDrop<Self>> {
+        Some(ManuallyDrop::new(Self {
+            ptr: NonNull::new(ptr)?,
+        }))
+    }
+
+    /// Sets an option of the context, the same as `stream_context_set_option()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `wrapper` - The name of the wrapper the option is for, such as `http`.
+    /// * `option` - The name of the option.
+    /// * `value` - The value of the option.
+    pub fn set_option(&mut self, wrapper: &str, option: &str, value: impl IntoZval) -> Result<()> {
+        let wrapper = CString::new(wrapper)?;
+        let option = CString::new(option)?;
+        let mut value = value.into_zval(false)?;
+
+        unsafe {
+            php_stream_context_set_option(
+                self.ptr.as_ptr(),
+                wrapper.as_ptr(),
+                option.as_ptr(),
+                &mut value,
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Returns the value of an option of the context. Returns [`None`] if the option has not
+    /// been set.
+    ///
+    /// # Parameters
+    ///
+    /// * `wrapper` - The name of the wrapper the option is for, such as `http`.
+    /// * `option` - The name of the option.
+    pub fn get_option(&self, wrapper: &str, option: &str) -> Option<&Zval> {
+        let wrapper = CString::new(wrapper).ok()?;
+        let option = CString::new(option).ok()?;
+
+        unsafe {
+            php_stream_context_get_option(self.ptr.as_ptr(), wrapper.as_ptr(), option.as_ptr())
+                .as_ref()
+        }
+    }
+
+    /// Returns the resource of the context.
+    fn resource(&self) -> *mut zend_resource {
+        unsafe { self.ptr.as_ref() }.res
+    }
+}
+
+impl Default for StreamContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for StreamContext {
+    fn drop(&mut self) {
+        unsafe { zend_list_delete(self.resource()) };
+    }
+}
+
+impl Debug for StreamContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamContext")
+            .field("ptr", &self.ptr)
+            .finish()
+    }
+}
+
+impl FromZval<'_> for StreamContext {
+    const TYPE: DataType = DataType::Resource;
+
+    // PHP has no type declaration for resources.
+    fn type_spec() -> TypeSpec {
+        DataType::Mixed.into()
+    }
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let res = unsafe { zval.resource()?.as_mut()? };
+        if res.type_ != unsafe { php_le_stream_context() } {
+            return None;
+        }
+
+        let ptr = NonNull::new(res.ptr as *mut php_stream_context)?;
+        res.inc_count();
+        Some(Self { ptr })
+    }
+}
+
+impl IntoZval for StreamContext {
+    const TYPE: DataType = DataType::Resource;
+
+    // PHP has no type declaration for resources.
+    fn type_spec() -> TypeSpec {
+        DataType::Mixed.into()
+    }
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        // The reference held by the context is moved into the zval.
+        zv.set_resource(self.resource());
+        std::mem::forget(self);
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Stream filters implemented in Rust, allowing PHP code to transform the data read from and
+//! written to streams with `stream_filter_append()` and `stream_filter_prepend()`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::io;
+//! use ext_php_rs::php::{
+//!     module::ModuleBuilder, stream_filter::StreamFilter, types::zval::Zval,
+//! };
+//!
+//! /// Applies the Caesar cipher to the stream, shifting letters by the number of places given
+//! /// as the parameter of the filter.
+//! struct Caesar {
+//!     shift: u8,
+//! }
+//!
+//! impl StreamFilter for Caesar {
+//!     fn create(_: &str, params: Option<&Zval>) -> Option<Self> {
+//!         let shift = params.and_then(|params| params.long()).unwrap_or(13);
+//!         Some(Self {
+//!             shift: shift.rem_euclid(26) as u8,
+//!         })
+//!     }
+//!
+//!     fn filter(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+//!         output.extend(input.iter().map(|&c| match c {
+//!             b'a'..=b'z' => (c - b'a' + self.shift) % 26 + b'a',
+//!             b'A'..=b'Z' => (c - b'A' + self.shift) % 26 + b'A',
+//!             c => c,
+//!         }));
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn get_module() -> *mut ext_php_rs::php::module::ModuleEntry {
+//!     // PHP: stream_filter_append($fp, 'myext.caesar', STREAM_FILTER_READ, 3);
+//!     ModuleBuilder::new("myext", "0.1.0")
+//!         .stream_filter::<Caesar>("myext.caesar")
+//!         .build()
+//!         .unwrap()
+//!         .into_raw()
+//! }
+//! ```
+
+use std::{
+    ffi::{c_void, CStr, CString},
+    io,
+    os::raw::{c_char, c_int},
+    ptr, slice,
+    sync::Arc,
+};
+
+use crate::{
+    bindings::{
+        ext_php_rs_php_stream_bucket_new, ext_php_rs_php_stream_filter_alloc, php_stream,
+        php_stream_bucket_append, php_stream_bucket_brigade, php_stream_bucket_delref,
+        php_stream_bucket_unlink, php_stream_filter, php_stream_filter_factory,
+        php_stream_filter_ops, php_stream_filter_register_factory, php_stream_filter_status_t,
+        php_stream_filter_status_t_PSFS_ERR_FATAL, php_stream_filter_status_t_PSFS_FEED_ME,
+        php_stream_filter_status_t_PSFS_PASS_ON, php_stream_filter_unregister_factory, size_t,
+        PSFS_FLAG_FLUSH_CLOSE, PSFS_FLAG_FLUSH_INC,
+    },
+    errors::{Error, Result},
+    php::{module::ModuleHook, types::zval::Zval},
+};
+
+/// A filter transforming the data passing through a stream, registered with
+/// [`ModuleBuilder::stream_filter`](crate::php::module::ModuleBuilder::stream_filter).
+///
+/// A new instance of the filter is created each time the filter is appended or prepended to a
+/// stream, and is dropped when it is removed from the stream or the stream is closed.
+pub trait StreamFilter: Sized + 'static {
+    /// Creates the filter when it is added to a stream. Returning [`None`] rejects the filter,
+    /// failing the call to `stream_filter_append()` with a warning.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name the filter was added with, which can be used to distinguish filters
+    ///   registered with a wildcard such as `myext.*`.
+    /// * `params` - The parameters given when adding the filter, if any.
+    fn create(name: &str, params: Option<&Zval>) -> Option<Self>;
+
+    /// Processes a chunk of the data passing through the stream, appending the transformed data
+    /// to the output. Data may be held back by the filter until more input is available or the
+    /// stream is flushed.
+    ///
+    /// Returning an error stops the data from passing through the stream.
+    ///
+    /// # Parameters
+    ///
+    /// * `input` - The data passing through the stream.
+    /// * `output` - The data passed on to the next filter.
+    fn filter(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<()>;
+
+    /// Appends any data held back by the filter to the output when the stream is flushed or
+    /// closed.
+    ///
+    /// # Parameters
+    ///
+    /// * `output` - The data passed on to the next filter.
+    /// * `closing` - Whether the stream is being closed, in which case no further data will
+    ///   pass through the filter.
+    fn flush(&mut self, output: &mut Vec<u8>, closing: bool) -> io::Result<()> {
+        let _ = (output, closing);
+        Ok(())
+    }
+}
+
+/// A registered filter. Allocated once per filter and never released, as PHP refers to it until
+/// the module shuts down.
+struct FactoryData {
+    name: CString,
+    factory: php_stream_filter_factory,
+}
+
+// SAFETY: The data is never modified after being allocated.
+unsafe impl Send for FactoryData {}
+unsafe impl Sync for FactoryData {}
+
+/// Creates the hooks registering and unregistering a filter when the module starts up and shuts
+/// down.
+pub(crate) fn hooks<T: StreamFilter>(name: &str) -> Result<(ModuleHook, ModuleHook)> {
+    let data: &'static FactoryData = Box::leak(Box::new(FactoryData {
+        name: CString::new(name)?,
+        factory: php_stream_filter_factory {
+            create_filter: Some(create_filter::<T>),
+        },
+    }));
+
+    let startup: ModuleHook = Arc::new(move |_| {
+        if unsafe { php_stream_filter_register_factory(data.name.as_ptr(), &data.factory) } < 0 {
+            Err(Error::StreamFilter(
+                data.name.to_string_lossy().into_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    });
+    let shutdown: ModuleHook = Arc::new(move |_| {
+        unsafe { php_stream_filter_unregister_factory(data.name.as_ptr()) };
+        Ok(())
+    });
+
+    Ok((startup, shutdown))
+}
+
+/// The state of a filter added to a stream, along with its table of functions.
+struct FilterState<T> {
+    ops: php_stream_filter_ops,
+    label: CString,
+    inner: T,
+}
+
+unsafe extern "C" fn create_filter<T: StreamFilter>(
+    filtername: *const c_char,
+    filterparams: *mut Zval,
+    persistent: u8,
+) -> *mut php_stream_filter {
+    let name = match CStr::from_ptr(filtername).to_str() {
+        Ok(name) => name,
+        Err(_) => return ptr::null_mut(),
+    };
+    let inner = match T::create(name, filterparams.as_ref()) {
+        Some(inner) => inner,
+        None => return ptr::null_mut(),
+    };
+
+    let mut state = Box::new(FilterState {
+        ops: php_stream_filter_ops {
+            filter: Some(filter::<T>),
+            dtor: Some(dtor::<T>),
+            label: ptr::null(),
+        },
+        label: CStr::from_ptr(filtername).to_owned(),
+        inner,
+    });
+    state.ops.label = state.label.as_ptr();
+
+    let ops: *const php_stream_filter_ops = &state.ops;
+    let state = Box::into_raw(state);
+    let filter = ext_php_rs_php_stream_filter_alloc(ops, state as *mut c_void, persistent);
+    if filter.is_null() {
+        drop(Box::from_raw(state));
+    }
+    filter
+}
+
+/// Returns the state of a filter given by PHP.
+unsafe fn filter_state<'a, T>(filter: *mut php_stream_filter) -> Option<&'a mut FilterState<T>> {
+    ((*filter).abstract_.value.ptr as *mut FilterState<T>).as_mut()
+}
+
+unsafe extern "C" fn filter<T: StreamFilter>(
+    stream: *mut php_stream,
+    thisfilter: *mut php_stream_filter,
+    buckets_in: *mut php_stream_bucket_brigade,
+    buckets_out: *mut php_stream_bucket_brigade,
+    bytes_consumed: *mut size_t,
+    flags: c_int,
+) -> php_stream_filter_status_t {
+    let state = match filter_state::<T>(thisfilter) {
+        Some(state) => state,
+        None => return php_stream_filter_status_t_PSFS_ERR_FATAL,
+    };
+    let mut output = Vec::new();
+    let mut consumed = 0;
+
+    while let Some(bucket) = (*buckets_in).head.as_mut() {
+        php_stream_bucket_unlink(bucket);
+        let result = if bucket.buflen == 0 {
+            Ok(())
+        } else {
+            let input = slice::from_raw_parts(bucket.buf as *const u8, bucket.buflen as _);
+            state.inner.filter(input, &mut output)
+        };
+        consumed += bucket.buflen;
+        php_stream_bucket_delref(bucket);
+
+        if result.is_err() {
+            return php_stream_filter_status_t_PSFS_ERR_FATAL;
+        }
+    }
+
+    let flags = flags as u32;
+    if flags & (PSFS_FLAG_FLUSH_INC | PSFS_FLAG_FLUSH_CLOSE) != 0
+        && state
+            .inner
+            .flush(&mut output, flags & PSFS_FLAG_FLUSH_CLOSE != 0)
+            .is_err()
+    {
+        return php_stream_filter_status_t_PSFS_ERR_FATAL;
+    }
+
+    if !bytes_consumed.is_null() {
+        *bytes_consumed = consumed;
+    }
+
+    if output.is_empty() {
+        return php_stream_filter_status_t_PSFS_FEED_ME;
+    }
+
+    let bucket = ext_php_rs_php_stream_bucket_new(
+        stream,
+        output.as_ptr() as *const c_char,
+        output.len() as _,
+        (*thisfilter).is_persistent as _,
+    );
+    if bucket.is_null() {
+        return php_stream_filter_status_t_PSFS_ERR_FATAL;
+    }
+    php_stream_bucket_append(buckets_out, bucket);
+
+    php_stream_filter_status_t_PSFS_PASS_ON
+}
+
+unsafe extern "C" fn dtor<T: StreamFilter>(thisfilter: *mut php_stream_filter) {
+    let ptr = (*thisfilter).abstract_.value.ptr as *mut FilterState<T>;
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+        (*thisfilter).abstract_.value.ptr = ptr::null_mut();
+    }
+}
@@ -0,0 +1,774 @@
+//! Stream wrappers implemented in Rust, allowing PHP code to open URLs such as `myfs://path` with
+//! `fopen()`, `file_get_contents()` and `include`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::{
+//!     collections::HashMap,
+//!     io::{self, Cursor, Read},
+//!     sync::{Arc, Mutex},
+//! };
+//! use ext_php_rs::php::{
+//!     module::ModuleBuilder,
+//!     stream::StreamContext,
+//!     stream_wrapper::{StreamStat, StreamWrapper, WrapperStream},
+//! };
+//!
+//! /// A read-only filesystem held in memory.
+//! #[derive(Default)]
+//! struct MemoryFs {
+//!     files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+//! }
+//!
+//! struct MemoryFile(Cursor<Vec<u8>>);
+//!
+//! impl WrapperStream for MemoryFile {
+//!     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+//!         self.0.read(buf)
+//!     }
+//! }
+//!
+//! impl StreamWrapper for MemoryFs {
+//!     type Stream = MemoryFile;
+//!
+//!     fn open(
+//!         &self,
+//!         path: &str,
+//!         _mode: &str,
+//!         _context: Option<&StreamContext>,
+//!     ) -> io::Result<MemoryFile> {
+//!         let files = self.files.lock().unwrap();
+//!         match files.get(path.trim_start_matches("memfs://")) {
+//!             Some(file) => Ok(MemoryFile(Cursor::new(file.clone()))),
+//!             None => Err(io::Error::new(io::ErrorKind::NotFound, "No such file")),
+//!         }
+//!     }
+//!
+//!     fn url_stat(&self, path: &str) -> io::Result<StreamStat> {
+//!         let files = self.files.lock().unwrap();
+//!         match files.get(path.trim_start_matches("memfs://")) {
+//!             Some(file) => Ok(StreamStat::file(file.len() as u64)),
+//!             None => Err(io::Error::new(io::ErrorKind::NotFound, "No such file")),
+//!         }
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn get_module() -> *mut ext_php_rs::php::module::ModuleEntry {
+//!     ModuleBuilder::new("memfs", "0.1.0")
+//!         .stream_wrapper("memfs", MemoryFs::default())
+//!         .build()
+//!         .unwrap()
+//!         .into_raw()
+//! }
+//! ```
+
+use std::{
+    convert::TryInto,
+    ffi::{c_void, CStr, CString},
+    io::{self, SeekFrom},
+    os::raw::{c_char, c_int},
+    ptr, slice,
+    sync::Arc,
+};
+
+use crate::{
+    bindings::{
+        ext_php_rs_php_stream_alloc, ext_php_rs_php_stream_statbuf_init,
+        php_register_url_stream_wrapper, php_stream, php_stream_context, php_stream_dirent,
+        php_stream_ops, php_stream_statbuf, php_stream_wrapper, php_stream_wrapper_log_error,
+        php_stream_wrapper_ops, php_unregister_url_stream_wrapper, size_t, ssize_t, zend_off_t,
+        zend_string, E_WARNING, PHP_STREAM_MKDIR_RECURSIVE, REPORT_ERRORS, SEEK_CUR, SEEK_END,
+        SEEK_SET,
+    },
+    errors::{Error, Result},
+    php::{
+        exceptions::error,
+        module::ModuleHook,
+        stream::{self, StreamContext},
+    },
+};
+
+/// The mode of regular files, `S_IFREG`.
+const MODE_FILE: u32 = 0o100000;
+/// The mode of directories, `S_IFDIR`.
+const MODE_DIRECTORY: u32 = 0o040000;
+
+/// Handles the URLs of a protocol registered with
+/// [`ModuleBuilder::stream_wrapper`](crate::php::module::ModuleBuilder::stream_wrapper).
+///
+/// The wrapper is shared by all requests, while each stream opened through the wrapper is
+/// represented by a [`WrapperStream`] holding the state of the stream. Operations which are not
+/// implemented fail, emitting a warning in PHP.
+pub trait StreamWrapper: Send + Sync + 'static {
+    /// The streams opened by the wrapper.
+    type Stream: WrapperStream;
+
+    /// Opens a stream, the same as `fopen()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL being opened, including the protocol.
+    /// * `mode` - The mode the stream is opened in, such as `"rb"`.
+    /// * `context` - The context passed to `fopen()`, if any.
+    fn open(
+        &self,
+        path: &str,
+        mode: &str,
+        context: Option<&StreamContext>,
+    ) -> io::Result<Self::Stream>;
+
+    /// Returns information about a URL, the same as `stat()` in PHP. Also used by functions such
+    /// as `file_exists()` and `is_dir()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL, including the protocol.
+    fn url_stat(&self, path: &str) -> io::Result<StreamStat> {
+        let _ = path;
+        Err(unsupported())
+    }
+
+    /// Deletes a file, the same as `unlink()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL of the file, including the protocol.
+    fn unlink(&self, path: &str) -> io::Result<()> {
+        let _ = path;
+        Err(unsupported())
+    }
+
+    /// Renames a file or directory, the same as `rename()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `from` - The current URL, including the protocol.
+    /// * `to` - The new URL, including the protocol.
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let _ = (from, to);
+        Err(unsupported())
+    }
+
+    /// Creates a directory, the same as `mkdir()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL of the directory, including the protocol.
+    /// * `mode` - The permissions of the directory.
+    /// * `recursive` - Whether to create the parent directories if they do not exist.
+    fn mkdir(&self, path: &str, mode: u32, recursive: bool) -> io::Result<()> {
+        let _ = (path, mode, recursive);
+        Err(unsupported())
+    }
+
+    /// Removes a directory, the same as `rmdir()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL of the directory, including the protocol.
+    fn rmdir(&self, path: &str) -> io::Result<()> {
+        let _ = path;
+        Err(unsupported())
+    }
+
+    /// Returns the names of the entries of a directory, the same as `opendir()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The URL of the directory, including the protocol.
+    fn opendir(&self, path: &str) -> io::Result<Vec<String>> {
+        let _ = path;
+        Err(unsupported())
+    }
+
+    /// Returns whether the wrapper accesses remote resources, in which case it is disabled when
+    /// `allow_url_fopen` is off.
+    fn is_url(&self) -> bool {
+        false
+    }
+}
+
+/// A stream opened through a [`StreamWrapper`], holding the state of the stream. The stream is
+/// dropped when it is closed in PHP.
+pub trait WrapperStream: 'static {
+    /// Reads from the stream into the given buffer, returning the number of bytes read. Returning
+    /// zero marks the end of the stream.
+    ///
+    /// # Parameters
+    ///
+    /// * `buf` - The buffer to read into.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Writes the given buffer to the stream, returning the number of bytes written.
+    ///
+    /// # Parameters
+    ///
+    /// * `buf` - The bytes to write.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let _ = buf;
+        Err(unsupported())
+    }
+
+    /// Flushes the data written to the stream.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Moves the position of the stream, returning the new position from the start of the
+    /// stream.
+    ///
+    /// # Parameters
+    ///
+    /// * `pos` - The position to move to.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let _ = pos;
+        Err(unsupported())
+    }
+
+    /// Returns information about the stream, the same as `fstat()` in PHP.
+    fn stat(&mut self) -> io::Result<StreamStat> {
+        Err(unsupported())
+    }
+}
+
+/// Information about a file or directory returned from a stream wrapper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamStat {
+    /// The type and permissions of the file, the same as `st_mode` in C.
+    pub mode: u32,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The time the file was last accessed, as a Unix timestamp.
+    pub atime: i64,
+    /// The time the file was last modified, as a Unix timestamp.
+    pub mtime: i64,
+    /// The time the file was last changed, as a Unix timestamp.
+    pub ctime: i64,
+}
+
+impl StreamStat {
+    /// Creates the information of a regular file of the given size, readable and writable by its
+    /// owner and readable by everyone else.
+    ///
+    /// # Parameters
+    ///
+    /// * `size` - The size of the file in bytes.
+    pub fn file(size: u64) -> Self {
+        Self {
+            mode: MODE_FILE | 0o644,
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Creates the information of a directory, accessible by everyone and writable by its owner.
+    pub fn directory() -> Self {
+        Self {
+            mode: MODE_DIRECTORY | 0o755,
+            ..Default::default()
+        }
+    }
+
+    /// Writes the information into a PHP stat buffer.
+    fn write_to(&self, ssb: *mut php_stream_statbuf) {
+        unsafe {
+            ext_php_rs_php_stream_statbuf_init(
+                ssb,
+                self.mode,
+                self.size as _,
+                self.atime,
+                self.mtime,
+                self.ctime,
+            )
+        };
+    }
+}
+
+/// Returns the error for operations not implemented by a wrapper.
+fn unsupported() -> io::Error {
+    stream::error("Operation is not supported by the stream wrapper.")
+}
+
+/// A registered wrapper, along with the tables of functions given to PHP. Allocated once per
+/// wrapper and never released, as PHP refers to it until the module shuts down.
+struct WrapperData<T> {
+    protocol: CString,
+    label: CString,
+    wrapper: php_stream_wrapper,
+    wops: php_stream_wrapper_ops,
+    ops: php_stream_ops,
+    dir_ops: php_stream_ops,
+    inner: T,
+}
+
+// SAFETY: The pointers in the data only refer to the data itself, which is never modified after
+// being allocated.
+unsafe impl<T: Send> Send for WrapperData<T> {}
+unsafe impl<T: Sync> Sync for WrapperData<T> {}
+
+/// Creates the hooks registering and unregistering a wrapper when the module starts up and
+/// shuts down.
+pub(crate) fn hooks<T: StreamWrapper>(
+    protocol: &str,
+    wrapper: T,
+) -> Result<(ModuleHook, ModuleHook)> {
+    let data: &'static mut WrapperData<T> = Box::leak(Box::new(WrapperData {
+        protocol: CString::new(protocol)?,
+        label: CString::new(protocol)?,
+        wrapper: php_stream_wrapper {
+            wops: ptr::null(),
+            abstract_: ptr::null_mut(),
+            is_url: wrapper.is_url() as _,
+        },
+        wops: php_stream_wrapper_ops {
+            wrapper_open: Some(wrapper_open::<T>),
+            wrapper_close: None,
+            wrapper_stat: None,
+            url_stat: Some(url_stat::<T>),
+            dir_opener: Some(dir_opener::<T>),
+            label: ptr::null(),
+            unlink: Some(unlink::<T>),
+            rename: Some(rename::<T>),
+            stream_mkdir: Some(mkdir::<T>),
+            stream_rmdir: Some(rmdir::<T>),
+            stream_metadata: None,
+        },
+        ops: php_stream_ops {
+            write: Some(stream_write::<T::Stream>),
+            read: Some(stream_read::<T::Stream>),
+            close: Some(stream_close::<T::Stream>),
+            flush: Some(stream_flush::<T::Stream>),
+            label: ptr::null(),
+            seek: Some(stream_seek::<T::Stream>),
+            cast: None,
+            stat: Some(stream_stat::<T::Stream>),
+            set_option: None,
+        },
+        dir_ops: php_stream_ops {
+            write: None,
+            read: Some(dir_read),
+            close: Some(dir_close),
+            flush: None,
+            label: ptr::null(),
+            seek: Some(dir_seek),
+            cast: None,
+            stat: None,
+            set_option: None,
+        },
+        inner: wrapper,
+    }));
+
+    data.wops.label = data.label.as_ptr();
+    data.ops.label = data.label.as_ptr();
+    data.dir_ops.label = data.label.as_ptr();
+    data.wrapper.wops = &data.wops;
+    data.wrapper.abstract_ = data as *mut WrapperData<T> as *mut c_void;
+    let data: &'static WrapperData<T> = data;
+
+    let startup: ModuleHook = Arc::new(move |_| {
+        if unsafe { php_register_url_stream_wrapper(data.protocol.as_ptr(), &data.wrapper) } < 0 {
+            Err(Error::StreamWrapper(
+                data.protocol.to_string_lossy().into_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    });
+    let shutdown: ModuleHook = Arc::new(move |_| {
+        unsafe { php_unregister_url_stream_wrapper(data.protocol.as_ptr()) };
+        Ok(())
+    });
+
+    Ok((startup, shutdown))
+}
+
+/// Returns the data of a wrapper given by PHP.
+unsafe fn wrapper_data<'a, T>(wrapper: *mut php_stream_wrapper) -> Option<&'a WrapperData<T>> {
+    ((*wrapper).abstract_ as *const WrapperData<T>).as_ref()
+}
+
+/// Converts a string given by PHP into a [`str`].
+unsafe fn to_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        CStr::from_ptr(ptr).to_str().ok()
+    }
+}
+
+/// Emits the error of a failed operation as a warning, if requested by PHP.
+fn report(options: c_int, op: &str, path: &str, err: &io::Error) {
+    if options as u32 & REPORT_ERRORS != 0 {
+        let _ = error(E_WARNING, &format!("{}({}): {}", op, path, err));
+    }
+}
+
+/// Converts the result of an operation into the return value expected by PHP, which is `1` for
+/// success.
+fn report_result(options: c_int, op: &str, path: &str, result: io::Result<()>) -> c_int {
+    match result {
+        Ok(_) => 1,
+        Err(e) => {
+            report(options, op, path, &e);
+            0
+        }
+    }
+}
+
+/// Opens a file, implementing `wrapper_open`.
+unsafe fn open<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    mode: *const c_char,
+    options: c_int,
+    context: *mut php_stream_context,
+) -> *mut php_stream {
+    let (data, path, mode) = match (wrapper_data::<T>(wrapper), to_str(filename), to_str(mode)) {
+        (Some(data), Some(path), Some(mode)) => (data, path, mode),
+        _ => return ptr::null_mut(),
+    };
+    let context = StreamContext::from_ptr_borrowed(context);
+
+    match data.inner.open(path, mode, context.as_deref()) {
+        Ok(stream) => ext_php_rs_php_stream_alloc(
+            &data.ops,
+            Box::into_raw(Box::new(stream)) as *mut c_void,
+            CString::new(mode).unwrap_or_default().as_ptr(),
+        ),
+        Err(e) => {
+            if let Ok(message) = CString::new(e.to_string()) {
+                php_stream_wrapper_log_error(
+                    wrapper,
+                    options,
+                    "%s\0".as_ptr() as _,
+                    message.as_ptr(),
+                );
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Opens a directory, implementing `dir_opener`.
+unsafe fn opendir<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    options: c_int,
+) -> *mut php_stream {
+    let (data, path) = match (wrapper_data::<T>(wrapper), to_str(filename)) {
+        (Some(data), Some(path)) => (data, path),
+        _ => return ptr::null_mut(),
+    };
+
+    match data.inner.opendir(path) {
+        Ok(entries) => ext_php_rs_php_stream_alloc(
+            &data.dir_ops,
+            Box::into_raw(Box::new(Directory { entries, pos: 0 })) as *mut c_void,
+            "r\0".as_ptr() as _,
+        ),
+        Err(e) => {
+            report(options, "opendir", path, &e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[cfg(not(php_debug))]
+unsafe extern "C" fn wrapper_open<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    mode: *const c_char,
+    options: c_int,
+    _: *mut *mut zend_string,
+    context: *mut php_stream_context,
+) -> *mut php_stream {
+    open::<T>(wrapper, filename, mode, options, context)
+}
+
+#[cfg(php_debug)]
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn wrapper_open<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    mode: *const c_char,
+    options: c_int,
+    _: *mut *mut zend_string,
+    context: *mut php_stream_context,
+    _: c_int,
+    _: *const c_char,
+    _: u32,
+    _: *const c_char,
+    _: u32,
+) -> *mut php_stream {
+    open::<T>(wrapper, filename, mode, options, context)
+}
+
+#[cfg(not(php_debug))]
+unsafe extern "C" fn dir_opener<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    _: *const c_char,
+    options: c_int,
+    _: *mut *mut zend_string,
+    _: *mut php_stream_context,
+) -> *mut php_stream {
+    opendir::<T>(wrapper, filename, options)
+}
+
+#[cfg(php_debug)]
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn dir_opener<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    filename: *const c_char,
+    _: *const c_char,
+    options: c_int,
+    _: *mut *mut zend_string,
+    _: *mut php_stream_context,
+    _: c_int,
+    _: *const c_char,
+    _: u32,
+    _: *const c_char,
+    _: u32,
+) -> *mut php_stream {
+    opendir::<T>(wrapper, filename, options)
+}
+
+unsafe extern "C" fn url_stat<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    url: *const c_char,
+    _: c_int,
+    ssb: *mut php_stream_statbuf,
+    _: *mut php_stream_context,
+) -> c_int {
+    let (data, path) = match (wrapper_data::<T>(wrapper), to_str(url)) {
+        (Some(data), Some(path)) => (data, path),
+        _ => return -1,
+    };
+
+    // PHP reports missing files itself where needed, such as in `stat()`.
+    match data.inner.url_stat(path) {
+        Ok(stat) => {
+            stat.write_to(ssb);
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+unsafe extern "C" fn unlink<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    url: *const c_char,
+    options: c_int,
+    _: *mut php_stream_context,
+) -> c_int {
+    match (wrapper_data::<T>(wrapper), to_str(url)) {
+        (Some(data), Some(path)) => report_result(options, "unlink", path, data.inner.unlink(path)),
+        _ => 0,
+    }
+}
+
+unsafe extern "C" fn rename<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    url_from: *const c_char,
+    url_to: *const c_char,
+    options: c_int,
+    _: *mut php_stream_context,
+) -> c_int {
+    match (wrapper_data::<T>(wrapper), to_str(url_from), to_str(url_to)) {
+        (Some(data), Some(from), Some(to)) => {
+            report_result(options, "rename", from, data.inner.rename(from, to))
+        }
+        _ => 0,
+    }
+}
+
+unsafe extern "C" fn mkdir<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    url: *const c_char,
+    mode: c_int,
+    options: c_int,
+    _: *mut php_stream_context,
+) -> c_int {
+    match (wrapper_data::<T>(wrapper), to_str(url)) {
+        (Some(data), Some(path)) => {
+            let recursive = options as u32 & PHP_STREAM_MKDIR_RECURSIVE != 0;
+            let result = data.inner.mkdir(path, mode as u32, recursive);
+            report_result(options, "mkdir", path, result)
+        }
+        _ => 0,
+    }
+}
+
+unsafe extern "C" fn rmdir<T: StreamWrapper>(
+    wrapper: *mut php_stream_wrapper,
+    url: *const c_char,
+    options: c_int,
+    _: *mut php_stream_context,
+) -> c_int {
+    match (wrapper_data::<T>(wrapper), to_str(url)) {
+        (Some(data), Some(path)) => report_result(options, "rmdir", path, data.inner.rmdir(path)),
+        _ => 0,
+    }
+}
+
+/// Returns the Rust stream held by a PHP stream.
+unsafe fn stream_state<'a, S>(stream: *mut php_stream) -> Option<&'a mut S> {
+    ((*stream).abstract_ as *mut S).as_mut()
+}
+
+unsafe extern "C" fn stream_read<S: WrapperStream>(
+    stream: *mut php_stream,
+    buf: *mut c_char,
+    count: size_t,
+) -> ssize_t {
+    let state = match stream_state::<S>(stream) {
+        Some(state) => state,
+        None => return -1,
+    };
+
+    match state.read(slice::from_raw_parts_mut(buf as *mut u8, count as _)) {
+        Ok(read) => {
+            if read == 0 && count > 0 {
+                (*stream).set_eof(1);
+            }
+            read as _
+        }
+        Err(_) => -1,
+    }
+}
+
+unsafe extern "C" fn stream_write<S: WrapperStream>(
+    stream: *mut php_stream,
+    buf: *const c_char,
+    count: size_t,
+) -> ssize_t {
+    let state = match stream_state::<S>(stream) {
+        Some(state) => state,
+        None => return -1,
+    };
+
+    match state.write(slice::from_raw_parts(buf as *const u8, count as _)) {
+        Ok(written) => written as _,
+        Err(_) => -1,
+    }
+}
+
+/// Drops the Rust state held by a PHP stream.
+unsafe fn release_state<S>(stream: *mut php_stream) -> c_int {
+    let ptr = (*stream).abstract_ as *mut S;
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+        (*stream).abstract_ = ptr::null_mut();
+    }
+    0
+}
+
+unsafe extern "C" fn stream_close<S: WrapperStream>(stream: *mut php_stream, _: c_int) -> c_int {
+    release_state::<S>(stream)
+}
+
+unsafe extern "C" fn stream_flush<S: WrapperStream>(stream: *mut php_stream) -> c_int {
+    match stream_state::<S>(stream).map(|state| state.flush()) {
+        Some(Ok(_)) => 0,
+        _ => -1,
+    }
+}
+
+unsafe extern "C" fn stream_seek<S: WrapperStream>(
+    stream: *mut php_stream,
+    offset: zend_off_t,
+    whence: c_int,
+    newoffset: *mut zend_off_t,
+) -> c_int {
+    let state = match stream_state::<S>(stream) {
+        Some(state) => state,
+        None => return -1,
+    };
+    let pos = match whence as u32 {
+        SEEK_SET => match offset.try_into() {
+            Ok(offset) => SeekFrom::Start(offset),
+            Err(_) => return -1,
+        },
+        SEEK_CUR => SeekFrom::Current(offset),
+        SEEK_END => SeekFrom::End(offset),
+        _ => return -1,
+    };
+
+    match state.seek(pos).map(|pos| pos.try_into()) {
+        Ok(Ok(pos)) => {
+            *newoffset = pos;
+            (*stream).set_eof(0);
+            0
+        }
+        _ => -1,
+    }
+}
+
+unsafe extern "C" fn stream_stat<S: WrapperStream>(
+    stream: *mut php_stream,
+    ssb: *mut php_stream_statbuf,
+) -> c_int {
+    match stream_state::<S>(stream).map(|state| state.stat()) {
+        Some(Ok(stat)) => {
+            stat.write_to(ssb);
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// The state of a directory stream.
+struct Directory {
+    entries: Vec<String>,
+    pos: usize,
+}
+
+unsafe extern "C" fn dir_read(stream: *mut php_stream, buf: *mut c_char, count: size_t) -> ssize_t {
+    let dir = match stream_state::<Directory>(stream) {
+        Some(dir) => dir,
+        None => return -1,
+    };
+    if count as usize != std::mem::size_of::<php_stream_dirent>() {
+        return -1;
+    }
+
+    let entry = match dir.entries.get(dir.pos) {
+        Some(entry) => entry,
+        None => {
+            (*stream).set_eof(1);
+            return 0;
+        }
+    };
+    dir.pos += 1;
+
+    let dirent = &mut *(buf as *mut php_stream_dirent);
+    let len = entry.len().min(dirent.d_name.len() - 1);
+    ptr::write_bytes(buf as *mut u8, 0, count as _);
+    ptr::copy_nonoverlapping(entry.as_ptr(), dirent.d_name.as_mut_ptr() as *mut u8, len);
+
+    count as _
+}
+
+unsafe extern "C" fn dir_close(stream: *mut php_stream, _: c_int) -> c_int {
+    release_state::<Directory>(stream)
+}
+
+unsafe extern "C" fn dir_seek(
+    stream: *mut php_stream,
+    offset: zend_off_t,
+    whence: c_int,
+    newoffset: *mut zend_off_t,
+) -> c_int {
+    // Only rewinding is supported, as done by `rewinddir()`.
+    match stream_state::<Directory>(stream) {
+        Some(dir) if offset == 0 && whence as u32 == SEEK_SET => {
+            dir.pos = 0;
+            *newoffset = 0;
+            (*stream).set_eof(0);
+            0
+        }
+        _ => -1,
+    }
+}
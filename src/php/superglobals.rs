@@ -0,0 +1,93 @@
+//! Access to the superglobals of the current request, such as `$_SERVER` and `$_GET`.
+//!
+//! Some superglobals, such as `$_SERVER`, are only populated when first used by a script. The
+//! functions in this module populate the superglobals as required, so they can be used even when
+//! the script does not refer to them.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ext_php_rs::php::superglobals;
+//!
+//! pub fn user_agent() -> Option<String> {
+//!     superglobals::request_header("User-Agent").map(|value| value.to_string())
+//! }
+//!
+//! pub fn page() -> i64 {
+//!     superglobals::get()
+//!         .and_then(|get| get.get("page"))
+//!         .and_then(|page| page.str())
+//!         .and_then(|page| page.parse().ok())
+//!         .unwrap_or(1)
+//! }
+//! ```
+
+use crate::{bindings::zend_is_auto_global_str, php::globals::ExecutorGlobals};
+
+use super::types::{array::HashTable, zval::Zval};
+
+/// Returns the `$_SERVER` superglobal, containing the server and execution environment.
+pub fn server() -> Option<&'static HashTable> {
+    superglobal("_SERVER")
+}
+
+/// Returns the `$_GET` superglobal, containing the parameters of the query string.
+pub fn get() -> Option<&'static HashTable> {
+    superglobal("_GET")
+}
+
+/// Returns the `$_POST` superglobal, containing the parameters of the request body.
+pub fn post() -> Option<&'static HashTable> {
+    superglobal("_POST")
+}
+
+/// Returns the `$_COOKIE` superglobal, containing the cookies sent with the request.
+pub fn cookie() -> Option<&'static HashTable> {
+    superglobal("_COOKIE")
+}
+
+/// Returns the `$_FILES` superglobal, containing the files uploaded with the request.
+pub fn files() -> Option<&'static HashTable> {
+    superglobal("_FILES")
+}
+
+/// Returns the `$_ENV` superglobal, containing the environment variables.
+pub fn env() -> Option<&'static HashTable> {
+    superglobal("_ENV")
+}
+
+/// Returns the `$_REQUEST` superglobal, containing the contents of `$_GET`, `$_POST` and
+/// `$_COOKIE` in the order given by the `request_order` INI setting.
+pub fn request() -> Option<&'static HashTable> {
+    superglobal("_REQUEST")
+}
+
+/// Returns the value of a header sent with the request, as found in `$_SERVER`.
+///
+/// # Parameters
+///
+/// * `name` - The name of the header, which is case-insensitive.
+pub fn request_header(name: &str) -> Option<&'static str> {
+    let key = format!("HTTP_{}", name.to_ascii_uppercase().replace('-', "_"));
+    deref(server()?.get(&key)?).str()
+}
+
+/// Returns a superglobal, populating it first if it is populated when first used.
+///
+/// # Parameters
+///
+/// * `name` - The name of the superglobal, without the leading `$`.
+fn superglobal(name: &str) -> Option<&'static HashTable> {
+    // SAFETY: The name is only read, and `zend_is_auto_global_str` does not require it to be
+    // NUL-terminated.
+    if !unsafe { zend_is_auto_global_str(name.as_ptr() as _, name.len() as _) } {
+        return None;
+    }
+
+    deref(ExecutorGlobals::get().symbol_table().get(name)?).array()
+}
+
+/// Returns the value a zval refers to, if it is a reference.
+fn deref(zval: &Zval) -> &Zval {
+    zval.reference().unwrap_or(zval)
+}
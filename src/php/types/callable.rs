@@ -1,11 +1,18 @@
 //! Types related to callables in PHP (anonymous functions, functions, etc).
 
-use std::ops::Deref;
+use std::{mem, ops::Deref, ptr};
 
-use super::zval::{IntoZvalDyn, Zval};
+use super::{
+    array::{HashTable, OwnedHashTable},
+    object::ZendObject,
+    zval::{IntoZval, IntoZvalDyn, Zval},
+};
 use crate::{
-    bindings::_call_user_function_impl,
+    bindings::{
+        _call_user_function_impl, ext_php_rs_zval_make_ref, zend_call_function, zend_fcall_info,
+    },
     errors::{Error, Result},
+    php::{class::ClassEntry, flags::ZendResult, globals::ExecutorGlobals},
 };
 
 /// Acts as a wrapper around a callable [`Zval`]. Allows the owner to call the [`Zval`] as if it
@@ -123,3 +130,273 @@ impl<'a> Callable<'a> {
         }
     }
 }
+
+/// Builds a call to a PHP function or method, supporting arguments passed by name and by
+/// reference. The arguments are given in the same order as they would be in PHP, with positional
+/// arguments before named arguments.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{errors::Result, php::types::{callable::CallBuilder, zval::Zval}};
+///
+/// /// Returns the first number in the given string.
+/// pub fn first_number(subject: &str) -> Result<Option<String>> {
+///     let mut matches = Zval::new();
+///
+///     // preg_match('/\d+/', $subject, $matches, offset: 0);
+///     let found = CallBuilder::function("preg_match")?
+///         .arg(r"/\d+/")
+///         .arg(subject)
+///         .arg_ref(&mut matches)
+///         .named_arg("offset", 0)
+///         .call()?;
+///
+///     if found.long() != Some(1) {
+///         return Ok(None);
+///     }
+///
+///     Ok(matches
+///         .array()
+///         .and_then(|matches| matches.get_index(0))
+///         .and_then(|number| number.str())
+///         .map(|number| number.to_string()))
+/// }
+/// ```
+pub struct CallBuilder<'a> {
+    function: Zval,
+    object: *mut ZendObject,
+    args: Vec<CallArg<'a>>,
+    named: Vec<(String, CallArg<'a>)>,
+    error: Option<Error>,
+}
+
+/// An argument of a call, either passed by value or by reference.
+enum CallArg<'a> {
+    Value(Zval),
+    Ref(&'a mut Zval),
+}
+
+impl<'a> CallArg<'a> {
+    /// Converts the argument into the zval passed to the function. Values passed by reference
+    /// are moved into a new reference, and must be written back with
+    /// [`write_back`](CallArg::write_back).
+    fn take_param(&mut self) -> Zval {
+        match self {
+            CallArg::Value(val) => mem::take(val),
+            CallArg::Ref(val) => {
+                let mut param = mem::take(*val);
+                // SAFETY: The zval is valid and owned, and is wrapped in a new reference.
+                unsafe { ext_php_rs_zval_make_ref(&mut param) };
+                param
+            }
+        }
+    }
+
+    /// Writes the value of a reference passed to the function back into the variable given by
+    /// the caller.
+    fn write_back(&mut self, param: &Zval) {
+        if let (CallArg::Ref(val), Some(new)) = (self, param.reference()) {
+            **val = new.shallow_clone();
+        }
+    }
+}
+
+impl<'a> CallBuilder<'a> {
+    /// Creates a call to a function, given by name.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the function, or of a static method in the form `Class::method`.
+    pub fn function(name: &str) -> Result<Self> {
+        let mut function = Zval::new();
+        function.set_string(name, false)?;
+        Ok(Self::new(function, ptr::null_mut()))
+    }
+
+    /// Creates a call to a value callable from PHP, such as a closure.
+    ///
+    /// # Parameters
+    ///
+    /// * `callable` - The value to call.
+    pub fn callable(callable: &Zval) -> Self {
+        Self::new(callable.shallow_clone(), ptr::null_mut())
+    }
+
+    /// Creates a call to a method of an object, the same as `$object->name()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to call the method on.
+    /// * `name` - The name of the method.
+    pub fn method(object: &'a ZendObject, name: &str) -> Result<Self> {
+        let mut function = Zval::new();
+        function.set_string(name, false)?;
+        Ok(Self::new(
+            function,
+            object as *const ZendObject as *mut ZendObject,
+        ))
+    }
+
+    /// Creates a call to a static method of a class, the same as `Class::name()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `ce` - The class to call the method on.
+    /// * `name` - The name of the method.
+    pub fn static_method(ce: &ClassEntry, name: &str) -> Result<Self> {
+        let class = ce.name().ok_or(Error::InvalidScope)?;
+        Self::function(&format!("{}::{}", class, name))
+    }
+
+    fn new(function: Zval, object: *mut ZendObject) -> Self {
+        Self {
+            function,
+            object,
+            args: vec![],
+            named: vec![],
+            error: None,
+        }
+    }
+
+    /// Adds a positional argument passed by value.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The value of the argument.
+    pub fn arg<T: IntoZval>(mut self, val: T) -> Self {
+        if let Some(val) = self.convert(val) {
+            self.args.push(CallArg::Value(val));
+        }
+        self
+    }
+
+    /// Adds a positional argument passed by reference. Changes made to the argument by the
+    /// function are written back to the given zval once the call returns.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The variable passed by reference.
+    pub fn arg_ref(mut self, val: &'a mut Zval) -> Self {
+        self.args.push(CallArg::Ref(val));
+        self
+    }
+
+    /// Adds an argument passed by value and matched to a parameter by name, the same as
+    /// `name: $val` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    /// * `val` - The value of the argument.
+    pub fn named_arg<T: IntoZval>(mut self, name: &str, val: T) -> Self {
+        if let Some(val) = self.convert(val) {
+            self.named.push((name.to_string(), CallArg::Value(val)));
+        }
+        self
+    }
+
+    /// Adds an argument passed by reference and matched to a parameter by name. Changes made to
+    /// the argument by the function are written back to the given zval once the call returns.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    /// * `val` - The variable passed by reference.
+    pub fn named_arg_ref(mut self, name: &str, val: &'a mut Zval) -> Self {
+        self.named.push((name.to_string(), CallArg::Ref(val)));
+        self
+    }
+
+    /// Converts an argument into a zval, storing the error to be returned by
+    /// [`call`](CallBuilder::call) if it fails.
+    fn convert<T: IntoZval>(&mut self, val: T) -> Option<Zval> {
+        match val.into_zval(false) {
+            Ok(val) => Some(val),
+            Err(e) => {
+                self.error.get_or_insert(e);
+                None
+            }
+        }
+    }
+
+    /// Calls the function, returning its return value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Callable`] if the function could not be called, such as when it does not
+    /// exist, or [`Error::Exception`] if the function threw an exception. Returns the error
+    /// of the first argument that could not be converted into a zval, if any.
+    pub fn call(self) -> Result<Zval> {
+        let Self {
+            function,
+            object,
+            mut args,
+            mut named,
+            error,
+        } = self;
+
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        // The named parameters are built first, as building them can fail, in which case the
+        // values passed by reference are restored before returning.
+        let mut named_params = if named.is_empty() {
+            None
+        } else {
+            let values: Vec<Zval> = named.iter_mut().map(|(_, arg)| arg.take_param()).collect();
+            let mut ht = OwnedHashTable::with_capacity(named.len() as _);
+            let inserted = named
+                .iter()
+                .zip(values.iter())
+                .try_for_each(|((name, _), value)| ht.insert(name, value.shallow_clone()));
+
+            if let Err(e) = inserted {
+                for ((_, arg), value) in named.iter_mut().zip(values.iter()) {
+                    arg.write_back(value);
+                }
+                return Err(e);
+            }
+            Some(ht)
+        };
+        let mut params: Vec<Zval> = args.iter_mut().map(CallArg::take_param).collect();
+
+        let mut retval = Zval::new();
+        let mut fci = zend_fcall_info {
+            size: mem::size_of::<zend_fcall_info>() as _,
+            function_name: function,
+            retval: &mut retval,
+            params: params.as_mut_ptr(),
+            object,
+            param_count: params.len() as _,
+            named_params: named_params
+                .as_mut()
+                .map(|ht| &mut **ht as *mut HashTable)
+                .unwrap_or_else(ptr::null_mut),
+        };
+
+        // SAFETY: The call info points to values which outlive the call, and the cache is
+        // filled in by `zend_call_function` when not given.
+        let result = unsafe { zend_call_function(&mut fci, ptr::null_mut()) };
+
+        for (arg, param) in args.iter_mut().zip(params.iter()) {
+            arg.write_back(param);
+        }
+        if let Some(ht) = named_params.as_ref() {
+            for (name, arg) in named.iter_mut() {
+                if let Some(param) = ht.get(name) {
+                    arg.write_back(param);
+                }
+            }
+        }
+
+        if result != ZendResult::Success.bits() {
+            Err(Error::Callable)
+        } else if !ExecutorGlobals::get().exception.is_null() {
+            Err(Error::Exception)
+        } else {
+            Ok(retval)
+        }
+    }
+}
@@ -283,7 +283,7 @@ macro_rules! php_closure_impl {
                         match $gen.val() {
                             Some(val) => val,
                             None => {
-                                let _ = PhpException::default(concat!("Invalid parameter type for `", stringify!($gen), "`.").into()).throw();
+                                $gen.throw_type_error();
                                 return;
                             }
                         }
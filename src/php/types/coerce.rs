@@ -0,0 +1,307 @@
+//! Conversion of zvals into Rust types following the rules PHP uses to coerce the arguments of
+//! functions, which depend on whether the caller declared `strict_types`.
+
+use std::{convert::TryInto, ptr};
+
+use crate::{
+    bindings::{
+        _is_numeric_string_ex, zval_get_string_func, E_DEPRECATED, E_WARNING, IS_DOUBLE, IS_LONG,
+    },
+    php::{
+        exceptions::error,
+        globals::ExecutorGlobals,
+        types::{
+            long::ZendLong,
+            string::{ZendStr, ZendString},
+            zval::{FromZval, Zval},
+        },
+    },
+};
+
+/// Allows zvals to be converted into Rust types using the coercion rules PHP applies to the
+/// arguments of internal functions.
+///
+/// In strict mode, values are only accepted if they are of the expected type, with the exception
+/// of integers which are accepted for floats. Otherwise, in coercive mode:
+///
+/// * Integers are accepted from floats within range, numeric strings and booleans. Floats with
+///   a fractional part are truncated with a deprecation notice.
+/// * Floats are accepted from integers, numeric strings and booleans.
+/// * Strings are accepted from integers, floats, booleans and objects implementing
+///   `__toString()`.
+/// * Booleans are accepted from integers, floats and strings.
+/// * `null` is accepted for all of the above, and is converted to zero, an empty string or
+///   `false`.
+///
+/// Strings which start with a number but contain trailing data, such as `"5 apples"`, are
+/// accepted as numbers with a warning.
+///
+/// Use [`Arg::coerced_val`](crate::php::args::Arg::coerced_val) to convert arguments using the
+/// mode of the caller of a function. Parameters of these types are converted this way in
+/// functions and methods exported with the [`macro@crate::php_function`] and
+/// [`macro@crate::php_impl`] macros.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     args::{Arg, ArgParser},
+///     enums::DataType,
+///     execution_data::ExecutionData,
+///     types::zval::Zval,
+/// };
+///
+/// pub extern "C" fn double(ex: &mut ExecutionData, retval: &mut Zval) {
+///     let mut number = Arg::new("number", DataType::Long);
+///     if ArgParser::new(ex).arg(&mut number).parse().is_err() {
+///         return;
+///     }
+///
+///     // Accepts `"21"` and `21.0` unless the caller declared `strict_types`.
+///     match number.coerced_val::<i64>() {
+///         Some(number) => retval.set_long(number * 2),
+///         None => retval.set_null(),
+///     }
+/// }
+/// ```
+pub trait FromZvalCoerced<'a>: FromZval<'a> {
+    /// Attempts to retrieve an instance of `Self` from a reference to a [`Zval`], coercing the
+    /// value if it is not of the expected type.
+    ///
+    /// # Parameters
+    ///
+    /// * `zval` - Zval to get value from.
+    /// * `strict` - Whether the value must be of the expected type.
+    fn from_zval_coerced(zval: &'a Zval, strict: bool) -> Option<Self>;
+}
+
+/// A number parsed from a numeric string.
+enum Numeric {
+    Long(ZendLong),
+    Double(f64),
+}
+
+/// Parses a numeric string, emitting a warning if the string contains trailing data. Returns
+/// [`None`] if the string is not numeric, or the warning was turned into an exception.
+fn parse_numeric(str: &ZendStr) -> Option<Numeric> {
+    let mut lval = 0;
+    let mut dval = 0.0;
+    let mut trailing_data = false;
+
+    // SAFETY: The string pointer and length are valid, and the out pointers point to locals.
+    let ty = unsafe {
+        _is_numeric_string_ex(
+            str.as_c_str().as_ptr(),
+            str.len() as _,
+            &mut lval,
+            &mut dval,
+            true,
+            ptr::null_mut(),
+            &mut trailing_data,
+        )
+    } as u32;
+
+    if trailing_data {
+        let _ = error(E_WARNING, "A non-numeric value encountered");
+        if !ExecutorGlobals::get().exception.is_null() {
+            return None;
+        }
+    }
+
+    match ty {
+        IS_LONG => Some(Numeric::Long(lval)),
+        IS_DOUBLE => Some(Numeric::Double(dval)),
+        _ => None,
+    }
+}
+
+/// Converts a float into an integer, rejecting values which are out of range. Emits a
+/// deprecation notice if the float has a fractional part, returning [`None`] if the notice was
+/// turned into an exception.
+///
+/// # Parameters
+///
+/// * `dval` - The float to convert.
+/// * `str` - The numeric string the float was parsed from, if any.
+fn double_to_long(dval: f64, str: Option<&ZendStr>) -> Option<ZendLong> {
+    if !(dval.is_finite() && dval >= ZendLong::MIN as f64 && dval < ZendLong::MAX as f64) {
+        return None;
+    }
+
+    if dval.fract() != 0.0 {
+        let message = match str.and_then(ZendStr::as_str) {
+            Some(str) => format!(
+                "Implicit conversion from float-string \"{}\" to int loses precision",
+                str
+            ),
+            None => format!(
+                "Implicit conversion from float {} to int loses precision",
+                dval
+            ),
+        };
+        let _ = error(E_DEPRECATED, &message);
+        if !ExecutorGlobals::get().exception.is_null() {
+            return None;
+        }
+    }
+
+    Some(dval as ZendLong)
+}
+
+/// Retrieves an integer from a zval, following `zend_parse_arg_long_weak`.
+fn coerce_long(zval: &Zval) -> Option<ZendLong> {
+    if let Some(lval) = zval.long() {
+        Some(lval)
+    } else if zval.is_double() {
+        double_to_long(zval.double()?, None)
+    } else if let Some(str) = zval.zend_str() {
+        match parse_numeric(str)? {
+            Numeric::Long(lval) => Some(lval),
+            Numeric::Double(dval) => double_to_long(dval, Some(str)),
+        }
+    } else if zval.is_null() {
+        Some(0)
+    } else {
+        zval.bool().map(ZendLong::from)
+    }
+}
+
+/// Retrieves a float from a zval, following `zend_parse_arg_double_weak`.
+fn coerce_double(zval: &Zval) -> Option<f64> {
+    if let Some(dval) = zval.double() {
+        Some(dval)
+    } else if let Some(str) = zval.zend_str() {
+        match parse_numeric(str)? {
+            Numeric::Long(lval) => Some(lval as f64),
+            Numeric::Double(dval) => Some(dval),
+        }
+    } else if zval.is_null() {
+        Some(0.0)
+    } else {
+        zval.bool().map(|bval| if bval { 1.0 } else { 0.0 })
+    }
+}
+
+/// Retrieves a boolean from a zval, following `zend_parse_arg_bool_weak`.
+fn coerce_bool(zval: &Zval) -> Option<bool> {
+    if let Some(bval) = zval.bool() {
+        Some(bval)
+    } else if let Some(lval) = zval.long() {
+        Some(lval != 0)
+    } else if let Some(dval) = zval.double() {
+        Some(dval != 0.0)
+    } else if let Some(str) = zval.zend_str() {
+        Some(!(str.is_empty() || str.as_c_str().to_bytes() == b"0"))
+    } else if zval.is_null() {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Retrieves a string from a zval, following `zend_parse_arg_str_weak`.
+fn coerce_string(zval: &Zval) -> Option<String> {
+    if let Some(str) = zval.str() {
+        return Some(str.to_string());
+    }
+
+    let convertible = zval.is_long()
+        || zval.is_double()
+        || zval.is_bool()
+        || zval.is_null()
+        || zval
+            .object()
+            .and_then(|obj| unsafe { obj.ce.as_ref() })
+            .map(|ce| !ce.__tostring.is_null())
+            .unwrap_or(false);
+
+    if !convertible {
+        return None;
+    }
+
+    // SAFETY: The zval is valid and is only read. The returned string is owned by the caller.
+    let str =
+        unsafe { ZendString::from_ptr(zval_get_string_func(zval as *const Zval as *mut Zval))? };
+
+    if !ExecutorGlobals::get().exception.is_null() {
+        return None;
+    }
+
+    str.as_str().map(|str| str.to_string())
+}
+
+macro_rules! from_zval_coerced_long {
+    ($($type: ty),*) => {
+        $(
+            impl FromZvalCoerced<'_> for $type {
+                fn from_zval_coerced(zval: &Zval, strict: bool) -> Option<Self> {
+                    if strict {
+                        zval.long()
+                    } else {
+                        coerce_long(zval)
+                    }
+                    .and_then(|val| val.try_into().ok())
+                }
+            }
+        )*
+    };
+}
+
+from_zval_coerced_long!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+impl FromZvalCoerced<'_> for f64 {
+    fn from_zval_coerced(zval: &Zval, strict: bool) -> Option<Self> {
+        if strict {
+            zval.double()
+        } else {
+            coerce_double(zval)
+        }
+    }
+}
+
+impl FromZvalCoerced<'_> for f32 {
+    fn from_zval_coerced(zval: &Zval, strict: bool) -> Option<Self> {
+        f64::from_zval_coerced(zval, strict).map(|val| val as f32)
+    }
+}
+
+impl FromZvalCoerced<'_> for bool {
+    fn from_zval_coerced(zval: &Zval, strict: bool) -> Option<Self> {
+        if strict {
+            zval.bool()
+        } else {
+            coerce_bool(zval)
+        }
+    }
+}
+
+impl FromZvalCoerced<'_> for String {
+    fn from_zval_coerced(zval: &Zval, strict: bool) -> Option<Self> {
+        if strict {
+            zval.str().map(|str| str.to_string())
+        } else {
+            coerce_string(zval)
+        }
+    }
+}
+
+/// Only strings are accepted, in both modes, as values converted into strings cannot be
+/// borrowed.
+impl<'a> FromZvalCoerced<'a> for &'a str {
+    fn from_zval_coerced(zval: &'a Zval, _: bool) -> Option<Self> {
+        zval.str()
+    }
+}
+
+impl<'a, T> FromZvalCoerced<'a> for Option<T>
+where
+    T: FromZvalCoerced<'a>,
+{
+    fn from_zval_coerced(zval: &'a Zval, strict: bool) -> Option<Self> {
+        if zval.is_null() {
+            Some(None)
+        } else {
+            T::from_zval_coerced(zval, strict).map(Some)
+        }
+    }
+}
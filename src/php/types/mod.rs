@@ -7,11 +7,14 @@ pub mod callable;
 #[cfg(any(docs, feature = "closure"))]
 #[cfg_attr(docs, doc(cfg(feature = "closure")))]
 pub mod closure;
+pub mod coerce;
 pub mod long;
 pub mod object;
 pub mod props;
 pub mod rc;
+pub mod reference;
 pub mod string;
+pub mod type_spec;
 pub mod zval;
 
 use std::{
@@ -18,9 +18,10 @@ use crate::{
     bindings::{
         ext_php_rs_zend_object_alloc, ext_php_rs_zend_object_release, object_properties_init,
         std_object_handlers, zend_is_true, zend_object, zend_object_handlers, zend_object_std_dtor,
-        zend_object_std_init, zend_objects_clone_members, zend_std_get_properties,
-        zend_std_has_property, zend_std_read_property, zend_std_write_property, zend_string,
-        HashTable, ZEND_ISEMPTY, ZEND_PROPERTY_EXISTS, ZEND_PROPERTY_ISSET,
+        zend_object_std_init, zend_objects_clone_members, zend_std_get_debug_info,
+        zend_std_get_properties, zend_std_has_property, zend_std_read_property,
+        zend_std_write_property, zend_string, HashTable, ZEND_ISEMPTY, ZEND_PROPERTY_EXISTS,
+        ZEND_PROPERTY_ISSET,
     },
     errors::{Error, Result},
     php::{
@@ -33,8 +34,9 @@ use crate::{
 };
 
 use super::{
+    callable::CallBuilder,
     props::Property,
-    zval::{FromZval, IntoZval, Zval},
+    zval::{FromZval, IntoZval, IntoZvalDyn, Zval},
 };
 
 pub type ZendObject = zend_object;
@@ -65,9 +67,11 @@ impl ZendObject {
     }
 
     /// Checks if the given object is an instance of a registered class with Rust
-    /// type `T`.
+    /// type `T`, or of a class inheriting from it.
     pub fn is_instance<T: RegisteredClass>(&self) -> bool {
-        (self.ce as *const ClassEntry).eq(&(T::get_metadata().ce() as *const _))
+        unsafe { self.ce.as_ref() }
+            .map(|ce| ce.instance_of(T::get_metadata().ce()))
+            .unwrap_or(false)
     }
 
     /// Attempts to read a property from the Object. Returns a result containing the
@@ -143,6 +147,22 @@ impl ZendObject {
         } > 0)
     }
 
+    /// Attempts to call a method of the object, the same as `$object->name(...$params)` in PHP.
+    /// Returns a result containing the return value of the method. Use [`CallBuilder::method`]
+    /// to pass arguments by name or by reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the method.
+    /// * `params` - A list of parameters to call the method with.
+    pub fn try_call_method(&self, name: &str, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        let mut call = CallBuilder::method(self, name)?;
+        for param in params {
+            call = call.arg(param.as_zval(false)?);
+        }
+        call.call()
+    }
+
     /// Attempts to retrieve the properties of the object. Returned inside a Zend Hashtable.
     pub fn get_properties(&self) -> Result<&HashTable> {
         unsafe {
@@ -153,6 +173,23 @@ impl ZendObject {
         }
     }
 
+    /// Returns a pointer to the Rust object of type `T` stored alongside the Zend object. Returns
+    /// [`None`] if the object is not an instance of the class registered for `T`.
+    ///
+    /// As well as objects of the class of `T`, this covers objects of PHP classes extending the
+    /// class and objects of registered classes which have `T` as their `#[parent]`. The Rust
+    /// object is located at the start of the allocation in all of these cases, and the handlers
+    /// of the object hold the offset of the Zend object from that start.
+    pub(crate) fn class_obj_ptr<T: RegisteredClass>(&self) -> Option<*mut T> {
+        if !self.is_instance::<T>() {
+            return None;
+        }
+
+        let offset = unsafe { self.handlers() }.ok()?.offset;
+        let ptr = unsafe { (self as *const Self as *const u8).sub(offset as usize) };
+        Some(ptr as *mut T)
+    }
+
     /// Attempts to retrieve a reference to the object handlers.
     #[inline]
     unsafe fn handlers(&self) -> Result<&ZendObjectHandlers> {
@@ -208,20 +245,29 @@ impl<'a, T: RegisteredClass> IntoZval for ClassRef<'a, T> {
     }
 }
 
+/// Wrapper struct around a PHP object holding a Rust object of type `T`.
+///
+/// The PHP object may be of a class inheriting from the class of `T`, in which case
+/// the wrapper dereferences to the `T` part of the object.
 pub struct ClassObject<'a, T: RegisteredClass> {
-    ptr: &'a mut ZendClassObject<T>,
+    std: &'a mut ZendObject,
     free: bool,
+    phantom: PhantomData<T>,
 }
 
 impl<T: RegisteredClass> Default for ClassObject<'_, T> {
     fn default() -> Self {
         let ptr = unsafe {
-            ZendClassObject::new_ptr(None)
+            ZendClassObject::<T>::new_ptr(None)
                 .as_mut()
                 .expect("Failed to allocate memory for class object.")
         };
 
-        Self { ptr, free: true }
+        Self {
+            std: ptr.get_mut_zend_obj(),
+            free: true,
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -233,63 +279,76 @@ impl<'a, T: RegisteredClass + 'a> ClassObject<'a, T> {
     /// * `obj` - The object to create a class object for.
     pub fn new(obj: T) -> Self {
         let ptr = unsafe {
-            ZendClassObject::new_ptr(Some(obj))
+            ZendClassObject::<T>::new_ptr(Some(obj))
                 .as_mut()
                 .expect("Failed to allocate memory for class object.")
         };
 
-        Self { ptr, free: true }
+        Self {
+            std: ptr.get_mut_zend_obj(),
+            free: true,
+            phantom: PhantomData,
+        }
     }
 
     /// Consumes the class object, releasing the internal pointer without releasing the internal object.
     ///
     /// Used to transfer ownership of the object to PHP.
-    pub(crate) fn into_raw(mut self) -> *mut ZendClassObject<T> {
+    pub(crate) fn into_raw(mut self) -> *mut ZendObject {
         self.free = false;
-        self.ptr
+        self.std
     }
 
-    /// Returns an immutable reference to the underlying class object.
-    pub(crate) fn internal(&self) -> &ZendClassObject<T> {
-        self.ptr
+    /// Returns an immutable reference to the underlying Zend object.
+    pub(crate) fn zend_obj(&self) -> &ZendObject {
+        self.std
     }
 
-    /// Returns a mutable reference to the underlying class object.
-    pub(crate) fn internal_mut(&mut self) -> &mut ZendClassObject<T> {
-        self.ptr
+    /// Returns a mutable reference to the underlying Zend object.
+    pub(crate) fn zend_obj_mut(&mut self) -> &mut ZendObject {
+        self.std
     }
 
-    /// Creates a new instance of [`ClassObject`] around a pre-existing class object.
+    /// Creates a new instance of [`ClassObject`] around a pre-existing Zend object. Returns
+    /// [`None`] if the object does not hold a Rust object of type `T`, which is the case when
+    /// the object is not an instance of the class of `T` or one of its subclasses.
     ///
     /// # Parameters
     ///
-    /// * `ptr` - Pointer to the class object.
+    /// * `ptr` - Pointer to the Zend object.
     /// * `free` - Whether to release the underlying object which `ptr` points to.
     ///
     /// # Safety
     ///
-    /// Caller must guarantee that `ptr` points to an aligned, non-null instance of
-    /// [`ZendClassObject`]. Caller must also guarantee that `ptr` will at least live for
-    /// the lifetime `'a` (as long as the resulting object lives).
+    /// Caller must guarantee that `ptr` will at least live for the lifetime `'a` (as long as
+    /// the resulting object lives).
     ///
     /// Caller must also guarantee that it is expected to free `ptr` after dropping the
     /// resulting [`ClassObject`] to prevent use-after-free situations.
-    ///
-    /// # Panics
-    ///
-    /// Panics when the given `ptr` is null.
-    pub(crate) unsafe fn from_zend_class_object(ptr: *mut ZendClassObject<T>, free: bool) -> Self {
-        Self {
-            ptr: ptr.as_mut().expect("Given pointer was null"),
+    pub(crate) unsafe fn from_zend_obj(ptr: *mut ZendObject, free: bool) -> Option<Self> {
+        let std = ptr.as_mut()?;
+        std.class_obj_ptr::<T>()?;
+
+        Some(Self {
+            std,
             free,
-        }
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns a pointer to the Rust object inside the class object.
+    fn obj_ptr(&self) -> *mut T {
+        // All constructors check that the object holds a `T`.
+        self.std
+            .class_obj_ptr::<T>()
+            .expect("Class object does not hold the expected type.")
     }
 }
 
 impl<T: RegisteredClass> Drop for ClassObject<'_, T> {
     fn drop(&mut self) {
         if self.free {
-            unsafe { ext_php_rs_zend_object_release(&mut (*self.ptr).std) };
+            unsafe { ext_php_rs_zend_object_release(self.std) };
         }
     }
 }
@@ -299,34 +358,40 @@ impl<T: RegisteredClass> Deref for ClassObject<'_, T> {
 
     fn deref(&self) -> &Self::Target {
         // SAFETY: Class object constructor guarantees memory is allocated.
-        unsafe { &*self.ptr.obj.as_ptr() }
+        unsafe { &*self.obj_ptr() }
     }
 }
 
 impl<T: RegisteredClass> DerefMut for ClassObject<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         // SAFETY: Class object constructor guarantees memory is allocated.
-        unsafe { &mut *self.ptr.obj.as_mut_ptr() }
+        unsafe { &mut *self.obj_ptr() }
     }
 }
 
 impl<T: RegisteredClass + Clone> Clone for ClassObject<'_, T> {
     fn clone(&self) -> Self {
-        // SAFETY: Class object constructor guarantees memory is allocated.
-        let mut new = Self::new(unsafe { &*self.internal().obj.as_ptr() }.clone());
-        unsafe {
-            zend_objects_clone_members(
-                &mut new.internal_mut().std,
-                &self.internal().std as *const _ as *mut _,
-            )
-        }
+        // The clone is allocated with the class of the source object, which may be a PHP subclass
+        // declaring additional properties, so that its property table fits the copied members.
+        let ce = unsafe { self.zend_obj().ce.as_ref() };
+        let ptr = unsafe {
+            ZendClassObject::<T>::new_ptr_with_ce(ce, Some((**self).clone()))
+                .as_mut()
+                .expect("Failed to allocate memory for class object.")
+        };
+        let mut new = Self {
+            std: ptr.get_mut_zend_obj(),
+            free: true,
+            phantom: PhantomData,
+        };
+        unsafe { zend_objects_clone_members(new.zend_obj_mut(), self.zend_obj().mut_ptr()) }
         new
     }
 }
 
 impl<T: RegisteredClass + Debug> Debug for ClassObject<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.internal().obj.fmt(f)
+        (**self).fmt(f)
     }
 }
 
@@ -334,7 +399,7 @@ impl<T: RegisteredClass> IntoZval for ClassObject<'_, T> {
     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
 
     fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
-        unsafe { zv.set_object(&mut (*self.into_raw()).std) };
+        unsafe { zv.set_object(&mut *self.into_raw()) };
 
         Ok(())
     }
@@ -352,9 +417,9 @@ impl<'a, T: RegisteredClass> FromZval<'a> for &'a T {
     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
-        let cobj = ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?;
+        let ptr = zval.object()?.class_obj_ptr::<T>()?;
 
-        Some(unsafe { &*cobj.obj.as_mut_ptr() })
+        Some(unsafe { &*ptr })
     }
 }
 
@@ -362,9 +427,9 @@ impl<'a, T: RegisteredClass> FromZval<'a> for &'a mut T {
     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
-        let cobj = ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?;
+        let ptr = zval.object()?.class_obj_ptr::<T>()?;
 
-        Some(unsafe { &mut *cobj.obj.as_mut_ptr() })
+        Some(unsafe { &mut *ptr })
     }
 }
 
@@ -432,6 +497,81 @@ where
     /// The key should be the name of the property and the value should be a reference to the property
     /// with reference to `self`. The value is a trait object for [`Prop`].
     fn get_properties<'a>() -> HashMap<&'static str, Property<'a, Self>>;
+
+    /// Returns the additional entries shown when the object is inspected from PHP, through
+    /// `var_dump`, `print_r` or a debugger such as Xdebug. The entries are shown after the
+    /// properties of the object, in the order they are returned.
+    ///
+    /// Returns [`None`] by default, in which case only the properties of the object are shown.
+    /// Usually implemented through the `#[debug_info]` attribute of the [`macro@php_class`]
+    /// macro, either from the [`Debug`] implementation of the type or from a [`PhpDebug`]
+    /// implementation.
+    ///
+    /// [`macro@php_class`]: crate::php_class
+    fn get_debug_info(&self) -> Option<Vec<(String, Zval)>> {
+        None
+    }
+}
+
+/// Implemented on registered classes which provide custom output when they are inspected from
+/// PHP with `var_dump`, `print_r` or a debugger.
+///
+/// Use the `#[debug_info(custom)]` attribute underneath the [`macro@php_class`] attribute to use
+/// this implementation for the class.
+///
+/// # Example
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// use ext_php_rs::php::types::{object::PhpDebug, zval::{IntoZval, Zval}};
+///
+/// #[php_class]
+/// #[debug_info(custom)]
+/// #[derive(Default)]
+/// pub struct Connection {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// impl PhpDebug for Connection {
+///     fn debug_info(&self) -> Vec<(String, Zval)> {
+///         let mut info = vec![];
+///         if let Ok(host) = self.host.as_str().into_zval(false) {
+///             info.push(("host".into(), host));
+///         }
+///         info.push(("port".into(), self.port.into()));
+///         info
+///     }
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
+/// [`macro@php_class`]: crate::php_class
+pub trait PhpDebug {
+    /// Returns an ordered list of name and value pairs to display for the object.
+    fn debug_info(&self) -> Vec<(String, Zval)>;
+}
+
+/// Builds the debug information for an object from its [`Debug`] implementation. Used by the
+/// `#[debug_info]` attribute of the [`macro@php_class`] macro.
+///
+/// The debug representation is placed under the `rust` key, as the structure of the type is not
+/// known outside of the [`Debug`] implementation.
+///
+/// # Parameters
+///
+/// * `obj` - The object to retrieve the debug information of.
+///
+/// [`macro@php_class`]: crate::php_class
+pub fn debug_info_from_fmt<T: Debug>(obj: &T) -> Vec<(String, Zval)> {
+    let mut zv = Zval::new();
+    match zv.set_string(&format!("{:#?}", obj), false) {
+        Ok(_) => vec![("rust".into(), zv)],
+        Err(_) => vec![],
+    }
 }
 
 /// Representation of a Zend class object in memory. Usually seen through its managed variant
@@ -446,9 +586,15 @@ impl<T: RegisteredClass> ZendClassObject<T> {
     /// Allocates memory for a new PHP object. The memory is allocated using the Zend memory manager,
     /// and therefore it is returned as a pointer.
     pub(crate) fn new_ptr(val: Option<T>) -> *mut Self {
+        Self::new_ptr_with_ce(None, val)
+    }
+
+    /// Allocates memory for a new PHP object of the given class, which must be the class of `T`
+    /// or a class inheriting from it. Uses the class of `T` if no class is given.
+    pub(crate) fn new_ptr_with_ce(ce: Option<&ClassEntry>, val: Option<T>) -> *mut Self {
         let size = mem::size_of::<Self>();
         let meta = T::get_metadata();
-        let ce = meta.ce() as *const _ as *mut _;
+        let ce = ce.unwrap_or_else(|| meta.ce()) as *const _ as *mut _;
         unsafe {
             let obj = (ext_php_rs_zend_object_alloc(size as _, ce) as *mut Self)
                 .as_mut()
@@ -466,6 +612,9 @@ impl<T: RegisteredClass> ZendClassObject<T> {
     /// Returns a reference to the [`ZendClassObject`] of a given object `T`. Returns [`None`]
     /// if the given object is not of the type `T`.
     ///
+    /// Objects of registered classes which have `T` as their `#[parent]` hold a different type,
+    /// and therefore the parent of such an object is not recognized.
+    ///
     /// # Parameters
     ///
     /// * `obj` - The object to get the [`ZendClassObject`] for.
@@ -477,7 +626,7 @@ impl<T: RegisteredClass> ZendClassObject<T> {
     pub(crate) unsafe fn from_obj_ptr(obj: &T) -> Option<&mut Self> {
         let ptr = (obj as *const T as *mut Self).as_mut()?;
 
-        if ptr.std.is_instance::<T>() {
+        if ptr.holds_type() {
             Some(ptr)
         } else {
             None
@@ -497,13 +646,20 @@ impl<T: RegisteredClass> ZendClassObject<T> {
             (ptr as *mut Self).as_mut()?
         };
 
-        if ptr.std.is_instance::<T>() {
+        if ptr.holds_type() {
             Some(ptr)
         } else {
             None
         }
     }
 
+    /// Checks if the class object was laid out for the type `T`, which is the case when the
+    /// Zend object uses the handlers of `T`. This is true for objects of PHP classes extending
+    /// the class of `T`, but not for registered classes which have `T` as their `#[parent]`.
+    fn holds_type(&self) -> bool {
+        ptr::eq(self.std.handlers, T::get_metadata().handlers())
+    }
+
     /// Returns a mutable reference to the underlying Zend object.
     pub(crate) fn get_mut_zend_obj(&mut self) -> &mut zend_object {
         &mut self.std
@@ -623,6 +779,7 @@ impl ZendObjectHandlers {
         (*ptr).write_property = Some(Self::write_property::<T>);
         (*ptr).get_properties = Some(Self::get_properties::<T>);
         (*ptr).has_property = Some(Self::has_property::<T>);
+        (*ptr).get_debug_info = Some(Self::get_debug_info::<T>);
     }
 
     unsafe extern "C" fn free_obj<T: RegisteredClass>(object: *mut zend_object) {
@@ -767,6 +924,53 @@ impl ZendObjectHandlers {
         props
     }
 
+    unsafe extern "C" fn get_debug_info<T: RegisteredClass>(
+        object: *mut zend_object,
+        is_temp: *mut c_int,
+    ) -> *mut HashTable {
+        #[inline(always)]
+        unsafe fn internal<T: RegisteredClass>(
+            object: *mut zend_object,
+        ) -> PhpResult<Option<OwnedHashTable>> {
+            let obj = object
+                .as_ref()
+                .and_then(|obj| ZendClassObject::<T>::from_zend_obj_ptr(obj))
+                .ok_or("Invalid object pointer given")?;
+            let info = match obj.obj.assume_init_ref().get_debug_info() {
+                Some(info) => info,
+                None => return Ok(None),
+            };
+
+            // Start with the properties of the object, including the Rust properties, then
+            // append the additional entries.
+            let mut table = match obj.std.get_properties() {
+                Ok(props) => props.to_owned(),
+                Err(_) => OwnedHashTable::new(),
+            };
+
+            for (name, val) in info {
+                table.insert(&name, val).map_err(|e| {
+                    format!("Failed to insert value into debug info hashtable: {:?}", e)
+                })?;
+            }
+
+            Ok(Some(table))
+        }
+
+        match internal::<T>(object) {
+            Ok(Some(table)) => {
+                // The table is newly allocated, so PHP must destroy it after use.
+                *is_temp = 1;
+                table.into_inner()
+            }
+            Ok(None) => zend_std_get_debug_info(object, is_temp),
+            Err(e) => {
+                let _ = e.throw();
+                zend_std_get_debug_info(object, is_temp)
+            }
+        }
+    }
+
     unsafe extern "C" fn has_property<T: RegisteredClass>(
         object: *mut zend_object,
         member: *mut zend_string,
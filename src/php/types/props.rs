@@ -133,6 +133,56 @@ impl<'a, T: 'a> Property<'a, T> {
         Self::Method { get, set }
     }
 
+    /// Converts the property into a property of a child type `C`, which holds `T` as its parent.
+    /// The getters and setters of the property are called on the parent retrieved from the child.
+    ///
+    /// Used to expose the properties of a parent class on classes using the `#[parent]` attribute.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent` - Function used to get a reference to the parent from the child.
+    /// * `parent_mut` - Function used to get a mutable reference to the parent from the child.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ext_php_rs::php::types::props::Property;
+    /// struct Base {
+    ///     pub a: i32,
+    /// }
+    ///
+    /// struct Child {
+    ///     pub base: Base,
+    /// }
+    ///
+    /// let prop: Property<Base> = Property::field(|base: &mut Base| &mut base.a);
+    /// let prop: Property<Child> = prop.inherit(|child| &child.base, |child| &mut child.base);
+    /// ```
+    pub fn inherit<C: 'static>(
+        self,
+        parent: fn(&C) -> &T,
+        parent_mut: fn(&mut C) -> &mut T,
+    ) -> Property<'a, C>
+    where
+        T: 'static,
+    {
+        match self {
+            Property::Field(field) => {
+                Property::field(move |child: &mut C| -> &mut dyn Prop { field(parent_mut(child)) })
+            }
+            Property::Method { get, set } => Property::Method {
+                get: get.map(|get| {
+                    Box::new(move |child: &C, retval: &mut Zval| get(parent(child), retval))
+                        as Box<dyn Fn(&C, &mut Zval) -> PhpResult + 'a>
+                }),
+                set: set.map(|set| {
+                    Box::new(move |child: &mut C, value: &Zval| set(parent_mut(child), value))
+                        as Box<dyn Fn(&mut C, &Zval) -> PhpResult + 'a>
+                }),
+            },
+        }
+    }
+
     /// Attempts to retrieve the value of the property from the given object `self_`.
     ///
     /// The value of the property, if successfully retrieved, is loaded into the given [`Zval`] `retval`. If
@@ -1,6 +1,6 @@
 //! Utilities for interacting with refcounted PHP types.
 
-use crate::bindings::{zend_refcounted_h, zend_string};
+use crate::bindings::{zend_refcounted_h, zend_resource, zend_string};
 
 use super::object::ZendObject;
 
@@ -47,4 +47,4 @@ macro_rules! rc {
     };
 }
 
-rc!(ZendObject, zend_string);
+rc!(ZendObject, zend_string, zend_resource);
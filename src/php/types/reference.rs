@@ -0,0 +1,120 @@
+//! Represents a parameter passed by reference to a function exported to PHP, such as the
+//! `$matches` parameter of `preg_match`.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::errors::Result;
+
+use super::zval::{FromZval, IntoZval, Zval};
+
+/// A parameter passed by reference. The value of the variable passed by the caller can be read,
+/// and replaced with a new value which the caller observes after the function returns.
+///
+/// Using this type as a parameter of a function exported with the [`macro@php_function`] or
+/// [`macro@php_impl`] macros marks the parameter as passed by reference. `T` is the type of
+/// the value which is read from and written to the reference. The value given by the caller may
+/// not be of type `T`, for example when an undefined variable is passed to be used as an output.
+///
+/// # Example
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// use ext_php_rs::php::types::{reference::ZendRef, zval::Zval};
+///
+/// #[php_function]
+/// pub fn increment(mut value: ZendRef<i64>) {
+///     let new = value.val().unwrap_or(0) + 1;
+///     value.set(new).expect("Failed to set reference");
+/// }
+///
+/// // `&mut Zval` parameters are also passed by reference.
+/// #[php_function]
+/// pub fn clear(value: &mut Zval) {
+///     value.set_null();
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
+/// [`macro@php_function`]: crate::php_function
+/// [`macro@php_impl`]: crate::php_impl
+pub struct ZendRef<'a, T> {
+    pub(crate) zv: &'a mut Zval,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T> ZendRef<'a, T> {
+    /// Creates a reference wrapper around the value of a PHP reference. Returns [`None`] if the
+    /// given zval is not a reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `zv` - The zval containing the reference.
+    ///
+    /// # Safety
+    ///
+    /// The wrapper grants mutable access to the referenced value. The caller must guarantee that
+    /// the referenced value is not accessed through any other reference for the lifetime `'a`,
+    /// including through another wrapper created from the same zval.
+    pub unsafe fn new(zv: &'a Zval) -> Option<Self> {
+        if !zv.is_reference() {
+            return None;
+        }
+
+        // The referenced value lives in a separate reference counted container, which outlives
+        // the zval holding the reference.
+        let reference = zv.value.ref_.as_mut()?;
+
+        Some(Self {
+            zv: &mut reference.val,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an immutable reference to the referenced value.
+    pub fn zval(&self) -> &Zval {
+        self.zv
+    }
+
+    /// Returns a mutable reference to the referenced value. Changes made to the value are
+    /// visible to the caller.
+    pub fn zval_mut(&mut self) -> &mut Zval {
+        self.zv
+    }
+
+    /// Consumes the wrapper, returning a mutable reference to the referenced value.
+    pub fn into_zval_mut(self) -> &'a mut Zval {
+        self.zv
+    }
+
+    /// Attempts to retrieve the referenced value as the type `T`. Returns [`None`] if the value
+    /// could not be converted.
+    pub fn val<'b>(&'b self) -> Option<T>
+    where
+        T: FromZval<'b>,
+    {
+        T::from_zval(self.zv)
+    }
+
+    /// Replaces the referenced value, releasing the previous value. The new value is visible to
+    /// the caller.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The new value of the reference.
+    pub fn set(&mut self, val: T) -> Result<()>
+    where
+        T: IntoZval,
+    {
+        *self.zv = val.into_zval(false)?;
+        Ok(())
+    }
+}
+
+impl<T> Debug for ZendRef<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ZendRef").field(self.zv).finish()
+    }
+}
@@ -65,6 +65,12 @@ impl ZendStr {
     pub fn as_str(&self) -> Option<&str> {
         self.as_c_str().to_str().ok()
     }
+
+    /// Returns the contents of the Zend string as bytes, which may include NUL characters.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: Zend strings store their readable length in a fat pointer.
+        unsafe { slice::from_raw_parts(self.val.as_ptr() as *const u8, self.len()) }
+    }
 }
 
 impl Debug for ZendStr {
@@ -253,6 +259,19 @@ impl ZendString {
         let this = ManuallyDrop::new(self);
         this.inner.as_ptr()
     }
+
+    /// Takes ownership of a pointer to a Zend string, releasing the string when dropped. Returns
+    /// [`None`] if the pointer is null.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to a valid Zend string, and the caller must own the reference to
+    /// the string which is being transferred.
+    pub(crate) unsafe fn from_ptr(ptr: *mut ZendStr) -> Option<Self> {
+        Some(Self {
+            inner: NonNull::new(ptr)?,
+        })
+    }
 }
 
 impl Drop for ZendString {
@@ -0,0 +1,426 @@
+//! Type declarations for the parameters and return values of functions exported to PHP, such as
+//! `int|string`, `?Foo` or `iterable`.
+
+use std::{
+    ffi::{c_void, CString},
+    fmt::Display,
+    ptr,
+};
+
+use crate::{
+    bindings::{
+        _ZEND_TYPE_NAME_BIT, _ZEND_TYPE_NULLABLE_BIT, IS_ITERABLE, IS_STATIC, MAY_BE_ANY,
+        MAY_BE_BOOL,
+    },
+    errors::{Error, Result},
+    php::enums::DataType,
+};
+
+use super::ZendType;
+
+/// Whether internal functions can declare unions containing more than one class, which is
+/// supported from PHP 8.1.
+const CLASS_UNIONS: bool = cfg!(php81);
+
+/// A PHP type declaration, used as the type of a parameter or the return type of a function.
+///
+/// Single types are created from a [`DataType`], where [`DataType::Mixed`] represents `mixed`
+/// and [`DataType::Object`] containing a class name represents that class.
+///
+/// # Example
+///
+/// ```
+/// use ext_php_rs::php::{enums::DataType, types::type_spec::TypeSpec};
+///
+/// let ty = TypeSpec::union(vec![DataType::Long.into(), DataType::String.into()]);
+/// assert_eq!(ty.to_string(), "int|string");
+///
+/// let ty = TypeSpec::class("Foo").nullable();
+/// assert_eq!(ty.to_string(), "?Foo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSpec {
+    /// A type represented by a data type.
+    Data(DataType),
+    /// A class or interface, given by name.
+    Class(String),
+    /// The `static` return type, which is the class the method was called on.
+    Static,
+    /// The `iterable` type, which is either an array or a `Traversable` object.
+    Iterable,
+    /// A value of any of the given types.
+    Union(Vec<TypeSpec>),
+    /// An object which is an instance of all of the given classes and interfaces.
+    Intersection(Vec<String>),
+    /// A value of the given type or `null`.
+    Nullable(Box<TypeSpec>),
+}
+
+impl TypeSpec {
+    /// Creates a type for an instance of a class or interface.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the class or interface.
+    pub fn class<T: Into<String>>(name: T) -> Self {
+        Self::Class(name.into())
+    }
+
+    /// Creates a type for a value of any of the given types. Unions and nullable types given as
+    /// members are flattened into the union, and duplicate members are removed.
+    ///
+    /// # Parameters
+    ///
+    /// * `types` - The types which make up the union.
+    pub fn union<T: IntoIterator<Item = TypeSpec>>(types: T) -> Self {
+        fn flatten(ty: TypeSpec, members: &mut Vec<TypeSpec>) {
+            match ty {
+                TypeSpec::Union(types) => types.into_iter().for_each(|ty| flatten(ty, members)),
+                TypeSpec::Nullable(ty) => {
+                    flatten(*ty, members);
+                    flatten(DataType::Null.into(), members);
+                }
+                ty if !members.contains(&ty) => members.push(ty),
+                _ => {}
+            }
+        }
+
+        let mut members = Vec::new();
+        for ty in types {
+            flatten(ty, &mut members);
+        }
+        Self::Union(members)
+    }
+
+    /// Creates a type for an object which is an instance of all of the given classes and
+    /// interfaces.
+    ///
+    /// # Parameters
+    ///
+    /// * `names` - The names of the classes and interfaces.
+    pub fn intersection<T, S>(names: T) -> Self
+    where
+        T: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Intersection(names.into_iter().map(Into::into).collect())
+    }
+
+    /// Makes the type accept `null`. Returns the type unchanged if it already accepts `null`.
+    pub fn nullable(self) -> Self {
+        if self.is_nullable() {
+            self
+        } else {
+            Self::Nullable(Box::new(self))
+        }
+    }
+
+    /// Returns whether the type accepts `null`.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Self::Data(DataType::Null | DataType::Mixed) | Self::Nullable(_) => true,
+            Self::Union(types) => types.iter().any(Self::is_nullable),
+            _ => false,
+        }
+    }
+
+    /// Returns the data type of the type if it is a single type, ignoring whether it accepts
+    /// `null`.
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            Self::Data(ty) => Some(*ty),
+            Self::Nullable(ty) => ty.data_type(),
+            _ => None,
+        }
+    }
+
+    /// Encodes the type into a Zend type.
+    ///
+    /// # Parameters
+    ///
+    /// * `pass_by_ref` - Whether the value should be passed by reference.
+    /// * `is_variadic` - Whether the type is for a variadic argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedType`] if the type cannot be declared on an internal function
+    /// with the version of PHP in use. PHP 8.0 does not support intersection types, or unions
+    /// containing more than one class, which are supported from PHP 8.1. Intersection types
+    /// cannot be part of a union or nullable. Returns [`Error::InvalidCString`] if a class name
+    /// contains NUL-bytes.
+    pub fn to_zend_type(&self, pass_by_ref: bool, is_variadic: bool) -> Result<ZendType> {
+        let flags = ZendType::arg_info_flags(pass_by_ref, is_variadic);
+
+        if let Self::Intersection(names) = self {
+            return Self::intersection_type(names, flags)
+                .ok_or_else(|| Error::UnsupportedType(self.to_string()))?;
+        }
+
+        let mut mask = 0;
+        let mut classes = Vec::new();
+        self.encode(&mut mask, &mut classes, CLASS_UNIONS)
+            .ok_or_else(|| Error::UnsupportedType(self.to_string()))?;
+
+        Ok(if classes.is_empty() {
+            ZendType {
+                ptr: ptr::null::<c_void>() as *mut c_void,
+                type_mask: mask | flags,
+            }
+        } else {
+            // Internal functions declare unions of classes as a single name separated by `|`,
+            // which PHP splits when the function is registered.
+            ZendType {
+                ptr: CString::new(classes.join("|"))?.into_raw() as *mut c_void,
+                type_mask: _ZEND_TYPE_NAME_BIT | mask | flags,
+            }
+        })
+    }
+
+    /// Builds the Zend type of an intersection type, which is a list of the classes, as
+    /// supported from PHP 8.1. Returns [`None`] if intersection types are not supported.
+    #[cfg(php81)]
+    fn intersection_type(names: &[String], flags: u32) -> Option<Result<ZendType>> {
+        use crate::{
+            bindings::{
+                ext_php_rs_pemalloc, zend_type_list, _ZEND_TYPE_INTERSECTION_BIT,
+                _ZEND_TYPE_LIST_BIT,
+            },
+            php::types::string::ZendString,
+        };
+        use std::mem;
+
+        if names.is_empty() {
+            return None;
+        }
+
+        // Equivalent to the `ZEND_TYPE_LIST_SIZE` macro. The list is persistent, and released
+        // by PHP when the function is unregistered.
+        let size =
+            mem::size_of::<zend_type_list>() + (names.len() - 1) * mem::size_of::<ZendType>();
+        let names = match names
+            .iter()
+            .map(|name| ZendString::new_interned(name, true).map(ZendString::into_inner))
+            .collect::<Result<Vec<_>>>()
+        {
+            Ok(names) => names,
+            Err(e) => return Some(Err(e)),
+        };
+
+        unsafe {
+            let list = ext_php_rs_pemalloc(size as _) as *mut zend_type_list;
+            (*list).num_types = names.len() as _;
+            for (i, name) in names.into_iter().enumerate() {
+                (*list).types.as_mut_ptr().add(i).write(ZendType {
+                    ptr: name as *mut c_void,
+                    type_mask: _ZEND_TYPE_NAME_BIT,
+                });
+            }
+
+            Some(Ok(ZendType {
+                ptr: list as *mut c_void,
+                type_mask: _ZEND_TYPE_LIST_BIT | _ZEND_TYPE_INTERSECTION_BIT | flags,
+            }))
+        }
+    }
+
+    /// Builds the Zend type of an intersection type. Returns [`None`] as intersection types are
+    /// not supported by PHP 8.0.
+    #[cfg(not(php81))]
+    fn intersection_type(_: &[String], _: u32) -> Option<Result<ZendType>> {
+        None
+    }
+
+    /// Adds the type to a type mask, storing the names of the classes the type contains. Returns
+    /// [`None`] if the type cannot be encoded.
+    ///
+    /// # Parameters
+    ///
+    /// * `mask` - The type mask to add the type to.
+    /// * `classes` - The names of the classes contained in the type.
+    /// * `class_unions` - Whether a type may contain more than one class.
+    fn encode<'a>(
+        &'a self,
+        mask: &mut u32,
+        classes: &mut Vec<&'a str>,
+        class_unions: bool,
+    ) -> Option<()> {
+        match self {
+            Self::Data(DataType::Object(Some(name))) => {
+                Self::encode_class(name, classes, class_unions)?
+            }
+            Self::Class(name) => Self::encode_class(name, classes, class_unions)?,
+            Self::Data(DataType::Bool) => *mask |= MAY_BE_BOOL,
+            Self::Data(DataType::Mixed) => *mask |= MAY_BE_ANY,
+            Self::Data(
+                DataType::Undef
+                | DataType::Reference
+                | DataType::ConstantExpression
+                | DataType::Ptr,
+            ) => return None,
+            Self::Data(ty) => *mask |= 1 << ty.as_u32(),
+            Self::Static => *mask |= 1 << IS_STATIC,
+            Self::Iterable => *mask |= 1 << IS_ITERABLE,
+            Self::Union(types) => {
+                for ty in types {
+                    ty.encode(mask, classes, class_unions)?;
+                }
+            }
+            Self::Nullable(ty) => {
+                ty.encode(mask, classes, class_unions)?;
+                *mask |= _ZEND_TYPE_NULLABLE_BIT;
+            }
+            Self::Intersection(_) => return None,
+        }
+
+        Some(())
+    }
+
+    /// Stores the name of a class contained in a type. Returns [`None`] if the type already
+    /// contains a different class and unions of classes are not supported.
+    fn encode_class<'a>(
+        name: &'a str,
+        classes: &mut Vec<&'a str>,
+        class_unions: bool,
+    ) -> Option<()> {
+        if classes
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(name))
+        {
+            return Some(());
+        }
+
+        if !classes.is_empty() && !class_unions {
+            return None;
+        }
+
+        classes.push(name);
+        Some(())
+    }
+}
+
+impl From<DataType> for TypeSpec {
+    fn from(ty: DataType) -> Self {
+        Self::Data(ty)
+    }
+}
+
+impl Display for TypeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Data(ty) => match ty {
+                DataType::Null => write!(f, "null"),
+                DataType::False => write!(f, "false"),
+                DataType::True => write!(f, "true"),
+                DataType::Long => write!(f, "int"),
+                DataType::Double => write!(f, "float"),
+                DataType::String => write!(f, "string"),
+                DataType::Array => write!(f, "array"),
+                DataType::Object(Some(name)) => write!(f, "{}", name),
+                DataType::Object(None) => write!(f, "object"),
+                DataType::Resource => write!(f, "resource"),
+                DataType::Callable => write!(f, "callable"),
+                DataType::Void => write!(f, "void"),
+                DataType::Mixed => write!(f, "mixed"),
+                DataType::Bool => write!(f, "bool"),
+                ty => write!(f, "{}", ty),
+            },
+            Self::Class(name) => write!(f, "{}", name),
+            Self::Static => write!(f, "static"),
+            Self::Iterable => write!(f, "iterable"),
+            Self::Union(types) => {
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+
+                    match ty {
+                        Self::Intersection(_) => write!(f, "({})", ty)?,
+                        ty => write!(f, "{}", ty)?,
+                    }
+                }
+                Ok(())
+            }
+            Self::Intersection(names) => write!(f, "{}", names.join("&")),
+            Self::Nullable(ty) => match &**ty {
+                Self::Union(_) => write!(f, "{}|null", ty),
+                Self::Intersection(_) => write!(f, "({})|null", ty),
+                ty => write!(f, "?{}", ty),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeSpec;
+    use crate::{
+        bindings::{
+            _ZEND_TYPE_NAME_BIT, _ZEND_TYPE_NULLABLE_BIT, IS_ITERABLE, IS_LONG, IS_NULL, IS_STRING,
+            MAY_BE_ANY,
+        },
+        errors::Error,
+        php::enums::DataType,
+    };
+
+    #[test]
+    fn test_encode_type_spec() {
+        let ty = TypeSpec::union(vec![DataType::Long.into(), DataType::String.into()]);
+        assert_eq!(
+            ty.to_zend_type(false, false).unwrap().type_mask,
+            (1 << IS_LONG) | (1 << IS_STRING)
+        );
+
+        let ty = TypeSpec::union(vec![TypeSpec::Iterable, DataType::Null.into()]);
+        assert_eq!(
+            ty.to_zend_type(false, false).unwrap().type_mask,
+            (1 << IS_ITERABLE) | (1 << IS_NULL)
+        );
+
+        let ty = TypeSpec::class("Foo").nullable();
+        let zend_type = ty.to_zend_type(false, false).unwrap();
+        assert_eq!(
+            zend_type.type_mask,
+            _ZEND_TYPE_NAME_BIT | _ZEND_TYPE_NULLABLE_BIT
+        );
+        assert!(!zend_type.ptr.is_null());
+
+        let ty = TypeSpec::from(DataType::Mixed).nullable();
+        assert_eq!(ty, TypeSpec::Data(DataType::Mixed));
+        assert_eq!(ty.to_zend_type(false, false).unwrap().type_mask, MAY_BE_ANY);
+    }
+
+    #[test]
+    fn test_unsupported_type_spec() {
+        let ty = TypeSpec::intersection(vec!["Countable", "Traversable"]);
+        assert_eq!(ty.to_string(), "Countable&Traversable");
+        assert_eq!(
+            ty.to_zend_type(false, false).unwrap_err(),
+            Error::UnsupportedType("Countable&Traversable".into())
+        );
+
+        let ty = TypeSpec::union(vec![TypeSpec::class("Foo"), TypeSpec::class("Bar")]);
+        assert_eq!(
+            ty.to_zend_type(false, false).unwrap_err(),
+            Error::UnsupportedType("Foo|Bar".into())
+        );
+    }
+
+    #[test]
+    fn test_encode_class_union() {
+        let ty = TypeSpec::union(vec![
+            TypeSpec::class("Foo"),
+            TypeSpec::class("Bar").nullable(),
+            TypeSpec::class("foo"),
+        ]);
+        assert_eq!(ty.to_string(), "Foo|Bar|null|foo");
+
+        let mut mask = 0;
+        let mut classes = Vec::new();
+        assert!(ty.encode(&mut mask, &mut classes, true).is_some());
+        assert_eq!(classes, vec!["Foo", "Bar"]);
+        assert_eq!(mask, 1 << IS_NULL);
+
+        let mut classes = Vec::new();
+        assert!(ty.encode(&mut 0, &mut classes, false).is_none());
+    }
+}
@@ -5,13 +5,14 @@ use std::{
     convert::{TryFrom, TryInto},
     ffi::c_void,
     fmt::Debug,
+    ops::Deref,
     ptr,
 };
 
 use crate::{
     bindings::{
-        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, zend_is_callable, zend_resource,
-        zend_value, zval, zval_ptr_dtor,
+        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, ext_php_rs_zval_separate,
+        zend_is_callable, zend_resource, zend_value, zval, zval_ptr_dtor,
     },
     errors::{Error, Result},
     php::{exceptions::PhpException, pack::Pack},
@@ -29,6 +30,7 @@ use super::{
     object::ZendObject,
     rc::PhpRc,
     string::ZendStr,
+    type_spec::TypeSpec,
 };
 
 /// Zend value. Represents most data types that are in the Zend engine.
@@ -190,6 +192,26 @@ impl Zval {
         }
     }
 
+    /// Returns the value the zval refers to if it is a reference, otherwise the zval itself.
+    /// Translation of the `ZVAL_DEREF` macro.
+    pub fn dereference(&self) -> &Zval {
+        self.reference().unwrap_or(self)
+    }
+
+    /// Returns a mutable reference to the value the zval refers to if it is a reference,
+    /// otherwise the zval itself. Changes made to a referenced value are visible to all holders
+    /// of the reference.
+    pub fn dereference_mut(&mut self) -> &mut Zval {
+        if self.is_reference() {
+            // SAFETY: The zval is a reference, so the reference is present.
+            if let Some(reference) = unsafe { self.value.ref_.as_mut() } {
+                return &mut reference.val;
+            }
+        }
+
+        self
+    }
+
     /// Returns the value of the zval if it is callable.
     pub fn callable(&self) -> Option<Callable> {
         // The Zval is checked if it is callable in the `new` function.
@@ -441,6 +463,39 @@ impl Zval {
         self.value.ptr = ptr as *mut c_void;
     }
 
+    /// Creates a copy of the zval which shares its contents, incrementing the reference count of
+    /// the contents when they are reference counted. Translation of the `ZVAL_COPY` macro.
+    ///
+    /// Strings and arrays are copy-on-write, and must be separated with [`Zval::separate`]
+    /// before they are modified through the copy. Objects and resources are shared, so changes
+    /// made through the copy are visible to all holders. When the zval is a reference, the copy
+    /// holds the same reference.
+    pub fn shallow_clone(&self) -> Zval {
+        // SAFETY: The reference count of the contents is incremented below to account for the
+        // new owner.
+        let new = unsafe { ptr::read(self) };
+
+        if ZvalTypeFlags::from_bits_truncate(unsafe { self.u1.type_info })
+            .contains(ZvalTypeFlags::RefCounted)
+        {
+            if let Some(counted) = unsafe { self.value.counted.as_mut() } {
+                counted.gc.refcount += 1;
+            }
+        }
+
+        new
+    }
+
+    /// Separates the contents of the zval from other holders, so that they can be modified without
+    /// the changes being visible elsewhere. Translation of the `SEPARATE_ZVAL` macro.
+    ///
+    /// References are unwrapped, replacing the zval with a copy of the referenced value. Arrays
+    /// and strings which are shared with other zvals are then duplicated. Objects and resources
+    /// remain shared.
+    pub fn separate(&mut self) {
+        unsafe { ext_php_rs_zval_separate(self) };
+    }
+
     /// Used to drop the Zval but keep the value of the zval intact.
     ///
     /// This is important when copying the value of the zval, as the actual value
@@ -505,12 +560,98 @@ impl Drop for Zval {
     }
 }
 
+impl Clone for Zval {
+    fn clone(&self) -> Self {
+        self.shallow_clone()
+    }
+}
+
 impl Default for Zval {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// A borrowed zval, such as an argument passed to a function. References are unwrapped, so the
+/// borrowed zval is the referenced value.
+///
+/// The borrow only lasts for the duration of the call. To hold onto the value beyond the call,
+/// convert it into an owned [`Zval`] with [`ZvalRef::to_zval`], which shares the contents with
+/// the caller rather than copying them.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ext_php_rs::prelude::*;
+/// use ext_php_rs::php::types::zval::{Zval, ZvalRef};
+///
+/// #[php_class]
+/// #[derive(Default)]
+/// pub struct Memo {
+///     last: Option<Zval>,
+/// }
+///
+/// #[php_impl]
+/// impl Memo {
+///     // Stores the value, returning whether its type differs from the previous value.
+///     pub fn remember(&mut self, value: ZvalRef) -> bool {
+///         let changed = self
+///             .last
+///             .as_ref()
+///             .map(|last| last.get_type() != value.get_type())
+///             .unwrap_or(true);
+///         self.last = Some(value.to_zval());
+///         changed
+///     }
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ZvalRef<'a> {
+    zv: &'a Zval,
+}
+
+impl<'a> ZvalRef<'a> {
+    /// Borrows a zval, unwrapping it if it is a reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `zv` - The zval to borrow.
+    pub fn new(zv: &'a Zval) -> Self {
+        Self {
+            zv: zv.dereference(),
+        }
+    }
+
+    /// Returns the borrowed zval, with the lifetime of the borrow.
+    pub fn zval(&self) -> &'a Zval {
+        self.zv
+    }
+
+    /// Creates an owned copy of the zval which shares its contents, incrementing their reference
+    /// count. See [`Zval::shallow_clone`].
+    pub fn to_zval(&self) -> Zval {
+        self.zv.shallow_clone()
+    }
+}
+
+impl Deref for ZvalRef<'_> {
+    type Target = Zval;
+
+    fn deref(&self) -> &Self::Target {
+        self.zv
+    }
+}
+
+impl Debug for ZvalRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.zv, f)
+    }
+}
+
 /// Provides implementations for converting Rust primitive types into PHP zvals. Alternative to the
 /// built-in Rust [`From`] and [`TryFrom`] implementations, allowing the caller to specify whether
 /// the Zval contents will persist between requests.
@@ -518,6 +659,13 @@ pub trait IntoZval: Sized {
     /// The corresponding type of the implemented value in PHP.
     const TYPE: DataType;
 
+    /// Returns the type declaration of the implemented value in PHP, used as the return type of
+    /// functions returning the value. Defaults to [`IntoZval::TYPE`], and can be overridden to
+    /// declare types which cannot be represented by a [`DataType`], such as unions.
+    fn type_spec() -> TypeSpec {
+        Self::TYPE.into()
+    }
+
     /// Converts a Rust primitive type into a Zval. Returns a result containing the Zval if
     /// successful.
     ///
@@ -681,6 +829,10 @@ where
 {
     const TYPE: DataType = T::TYPE;
 
+    fn type_spec() -> TypeSpec {
+        T::type_spec().nullable()
+    }
+
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
         match self {
             Some(val) => val.set_zval(zv, persistent),
@@ -699,6 +851,10 @@ where
 {
     const TYPE: DataType = T::TYPE;
 
+    fn type_spec() -> TypeSpec {
+        T::type_spec()
+    }
+
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
         match self {
             Ok(val) => val.set_zval(zv, persistent),
@@ -716,6 +872,57 @@ pub trait FromZval<'a>: Sized {
     /// The corresponding type of the implemented value in PHP.
     const TYPE: DataType;
 
+    /// Returns the type declaration of the implemented value in PHP, used as the type of
+    /// parameters accepting the value. Defaults to [`FromZval::TYPE`], and can be overridden to
+    /// declare types which cannot be represented by a [`DataType`], such as the union of the
+    /// variants of an enum. Enums can also derive the trait with [`macro@crate::FromZval`],
+    /// which declares the union of the variants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ext_php_rs::prelude::*;
+    /// use ext_php_rs::php::{
+    ///     enums::DataType,
+    ///     types::{type_spec::TypeSpec, zval::{FromZval, Zval}},
+    /// };
+    ///
+    /// pub enum IntOrString {
+    ///     Int(i64),
+    ///     String(String),
+    /// }
+    ///
+    /// impl FromZval<'_> for IntOrString {
+    ///     const TYPE: DataType = DataType::Mixed;
+    ///
+    ///     fn type_spec() -> TypeSpec {
+    ///         TypeSpec::union(vec![DataType::Long.into(), DataType::String.into()])
+    ///     }
+    ///
+    ///     fn from_zval(zval: &Zval) -> Option<Self> {
+    ///         zval.long()
+    ///             .map(IntOrString::Int)
+    ///             .or_else(|| zval.string().map(IntOrString::String))
+    ///     }
+    /// }
+    ///
+    /// // The parameter is declared as `int|string`.
+    /// #[php_function]
+    /// pub fn describe(value: IntOrString) -> String {
+    ///     match value {
+    ///         IntOrString::Int(i) => format!("int {}", i),
+    ///         IntOrString::String(s) => format!("string {}", s),
+    ///     }
+    /// }
+    /// # #[php_module]
+    /// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+    /// #     module
+    /// # }
+    /// ```
+    fn type_spec() -> TypeSpec {
+        Self::TYPE.into()
+    }
+
     /// Attempts to retrieve an instance of `Self` from a reference to a [`Zval`].
     ///
     /// # Parameters
@@ -730,6 +937,10 @@ where
 {
     const TYPE: DataType = T::TYPE;
 
+    fn type_spec() -> TypeSpec {
+        T::type_spec().nullable()
+    }
+
     fn from_zval(zval: &'a Zval) -> Option<Self> {
         Some(T::from_zval(zval))
     }
@@ -788,6 +999,24 @@ impl<'a> FromZval<'a> for &'a str {
     }
 }
 
+/// Takes ownership of a copy of the value, which may be held beyond the function call. References
+/// are unwrapped, so the copy holds the referenced value.
+impl FromZval<'_> for Zval {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        Some(zval.dereference().shallow_clone())
+    }
+}
+
+impl<'a> FromZval<'a> for ZvalRef<'a> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        Some(ZvalRef::new(zval))
+    }
+}
+
 impl<'a> FromZval<'a> for Callable<'a> {
     const TYPE: DataType = DataType::Callable;
 
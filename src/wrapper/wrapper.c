@@ -37,3 +37,143 @@ zend_executor_globals *ext_php_rs_executor_globals()
     return &executor_globals;
 #endif
 }
+
+php_stream *ext_php_rs_php_stream_open_wrapper(const char *path, const char *mode, int options, php_stream_context *context)
+{
+    return php_stream_open_wrapper_ex(path, mode, options, NULL, context);
+}
+
+void ext_php_rs_php_stream_to_zval(php_stream *stream, zval *zv)
+{
+    php_stream_to_zval(stream, zv);
+}
+
+php_stream *ext_php_rs_php_stream_alloc(const php_stream_ops *ops, void *abstract, const char *mode)
+{
+    return php_stream_alloc(ops, abstract, NULL, mode);
+}
+
+void ext_php_rs_php_stream_statbuf_init(php_stream_statbuf *ssb, uint32_t mode, zend_off_t size, int64_t atime, int64_t mtime, int64_t ctime)
+{
+    memset(ssb, 0, sizeof(*ssb));
+    ssb->sb.st_mode = mode;
+    ssb->sb.st_size = size;
+    ssb->sb.st_nlink = 1;
+    ssb->sb.st_atime = atime;
+    ssb->sb.st_mtime = mtime;
+    ssb->sb.st_ctime = ctime;
+}
+
+php_stream_filter *ext_php_rs_php_stream_filter_alloc(const php_stream_filter_ops *fops, void *abstract, uint8_t persistent)
+{
+    return php_stream_filter_alloc(fops, abstract, persistent);
+}
+
+php_stream_bucket *ext_php_rs_php_stream_bucket_new(php_stream *stream, const char *buf, size_t buflen, uint8_t persistent)
+{
+    char *copy = pemalloc(buflen, persistent);
+    memcpy(copy, buf, buflen);
+    return php_stream_bucket_new(stream, copy, buflen, 1, persistent);
+}
+
+sapi_globals_struct *ext_php_rs_sapi_globals()
+{
+#ifdef ZTS
+# ifdef ZEND_ENABLE_STATIC_TSRMLS_CACHE
+    return TSRMG_FAST_BULK_STATIC(sapi_globals_offset, sapi_globals_struct *);
+# else
+    return TSRMG_FAST_BULK(sapi_globals_offset, sapi_globals_struct *);
+# endif
+#else
+    return &sapi_globals;
+#endif
+}
+
+void ext_php_rs_zval_make_ref(zval *zv)
+{
+    ZVAL_MAKE_REF(zv);
+}
+
+void ext_php_rs_zval_separate(zval *zv)
+{
+    SEPARATE_ZVAL(zv);
+    if (Z_TYPE_P(zv) == IS_STRING && Z_REFCOUNTED_P(zv)) {
+        SEPARATE_STRING(zv);
+    }
+}
+
+void ext_php_rs_zval_persist(zval *zv)
+{
+    switch (Z_TYPE_P(zv)) {
+        case IS_STRING: {
+            zend_string *str = zend_string_init_interned(Z_STRVAL_P(zv), Z_STRLEN_P(zv), 1);
+            zval_ptr_dtor(zv);
+            ZVAL_INTERNED_STR(zv, str);
+            break;
+        }
+        case IS_ARRAY: {
+            HashTable *src = Z_ARRVAL_P(zv);
+            HashTable *dst = pemalloc(sizeof(HashTable), 1);
+            zend_string *key;
+            zend_ulong idx;
+            zval *val;
+
+            zend_hash_init(dst, zend_hash_num_elements(src), NULL, NULL, 1);
+            ZEND_HASH_FOREACH_KEY_VAL(src, idx, key, val) {
+                zval copy;
+                ZVAL_COPY(&copy, val);
+                ext_php_rs_zval_persist(&copy);
+                if (key) {
+                    zend_hash_add_new(dst, zend_string_init_interned(ZSTR_VAL(key), ZSTR_LEN(key), 1), &copy);
+                } else {
+                    zend_hash_index_add_new(dst, idx, &copy);
+                }
+            } ZEND_HASH_FOREACH_END();
+
+            GC_ADD_FLAGS(dst, IS_ARRAY_IMMUTABLE);
+            zval_ptr_dtor(zv);
+            ZVAL_ARR(zv, dst);
+            Z_TYPE_FLAGS_P(zv) = 0;
+            break;
+        }
+    }
+}
+
+void ext_php_rs_zval_persist_release(zval *zv)
+{
+    if (Z_TYPE_P(zv) == IS_ARRAY) {
+        HashTable *ht = Z_ARRVAL_P(zv);
+        zval *val;
+
+        ZEND_HASH_FOREACH_VAL(ht, val) {
+            ext_php_rs_zval_persist_release(val);
+        } ZEND_HASH_FOREACH_END();
+
+        zend_hash_destroy(ht);
+        pefree(ht, 1);
+    }
+    ZVAL_UNDEF(zv);
+}
+
+void ext_php_rs_info_print_box(const char *content)
+{
+    php_info_print_box_start(0);
+    if (sapi_module.phpinfo_as_text) {
+        PHPWRITE(content, strlen(content));
+    } else {
+        zend_string *escaped = php_info_html_esc(content);
+        PHPWRITE(ZSTR_VAL(escaped), ZSTR_LEN(escaped));
+        zend_string_free(escaped);
+    }
+    php_info_print_box_end();
+}
+
+void *ext_php_rs_pemalloc(size_t size)
+{
+    return pemalloc(size, 1);
+}
+
+void ext_php_rs_pefree(void *ptr)
+{
+    pefree(ptr, 1);
+}
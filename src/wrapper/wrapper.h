@@ -1,11 +1,29 @@
 #include "php.h"
 #include "ext/standard/info.h"
+#include "php_ini.h"
+#include "zend_builtin_functions.h"
 #include "zend_exceptions.h"
 #include "zend_inheritance.h"
+#include "zend_observer.h"
+#include "SAPI.h"
 
 zend_string *ext_php_rs_zend_string_init(const char *str, size_t len, bool persistent);
 void ext_php_rs_zend_string_release(zend_string *zs);
 const char *ext_php_rs_php_build_id();
 void *ext_php_rs_zend_object_alloc(size_t obj_size, zend_class_entry *ce);
 void ext_php_rs_zend_object_release(zend_object *obj);
-zend_executor_globals *ext_php_rs_executor_globals();
\ No newline at end of file
+zend_executor_globals *ext_php_rs_executor_globals();
+php_stream *ext_php_rs_php_stream_open_wrapper(const char *path, const char *mode, int options, php_stream_context *context);
+void ext_php_rs_php_stream_to_zval(php_stream *stream, zval *zv);
+php_stream *ext_php_rs_php_stream_alloc(const php_stream_ops *ops, void *abstract, const char *mode);
+void ext_php_rs_php_stream_statbuf_init(php_stream_statbuf *ssb, uint32_t mode, zend_off_t size, int64_t atime, int64_t mtime, int64_t ctime);
+php_stream_filter *ext_php_rs_php_stream_filter_alloc(const php_stream_filter_ops *fops, void *abstract, uint8_t persistent);
+php_stream_bucket *ext_php_rs_php_stream_bucket_new(php_stream *stream, const char *buf, size_t buflen, uint8_t persistent);
+sapi_globals_struct *ext_php_rs_sapi_globals();
+void ext_php_rs_zval_make_ref(zval *zv);
+void ext_php_rs_zval_persist(zval *zv);
+void ext_php_rs_zval_persist_release(zval *zv);
+void ext_php_rs_zval_separate(zval *zv);
+void ext_php_rs_info_print_box(const char *content);
+void *ext_php_rs_pemalloc(size_t size);
+void ext_php_rs_pefree(void *ptr);